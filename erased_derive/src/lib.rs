@@ -0,0 +1,54 @@
+//! Derive macros for the [`erased`](https://docs.rs/erased) crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Literal;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Index, Member};
+
+/// Derive an `erased_fields(&self)` method, which returns a `HashMap` from the name of each field to an `Erased` reference to that field.
+///
+/// Fields of tuple structs are named by their index, for example `"0"`.
+/// Raw identifiers are named without their `r#` prefix, so a field `r#type` is named `"type"`.
+#[proc_macro_derive(ErasedFields)]
+pub fn derive_erased_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let Data::Struct(data) = &input.data else {
+        return Error::new_spanned(
+            &input.ident,
+            "`ErasedFields` can only be derived for structs",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let (names, members): (Vec<_>, Vec<_>) = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => (
+                Literal::string(&ident.unraw().to_string()),
+                Member::Named(ident.clone()),
+            ),
+            None => (
+                Literal::string(&i.to_string()),
+                Member::Unnamed(Index::from(i)),
+            ),
+        })
+        .unzip();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Get an erased reference to each field of this struct, by field name
+            pub fn erased_fields(&self) -> ::std::collections::HashMap<&'static str, ::erased::Erased<'_>> {
+                ::std::collections::HashMap::from([
+                    #((#names, ::erased::Erased::new(&self.#members)),)*
+                ])
+            }
+        }
+    }
+    .into()
+}
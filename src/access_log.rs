@@ -0,0 +1,102 @@
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ptr::NonNull;
+
+/// The maximum number of entries kept in the access log of each thread.
+pub const ACCESS_LOG_CAPACITY: usize = 64;
+
+thread_local! {
+    static ACCESS_LOG: RefCell<VecDeque<AccessLogEntry>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// A reference that was erased using `Erased::new_logged`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AccessLogEntry {
+    /// The address of the value that was erased
+    pub address: NonNull<()>,
+    /// The name of the type of the value that was erased, as given by `std::any::type_name`
+    pub type_name: &'static str,
+}
+
+/// Push an entry for `ptr` to the access log of the current thread, evicting the oldest entry if the log is full.
+pub(crate) fn log<T>(ptr: NonNull<()>) {
+    ACCESS_LOG.with_borrow_mut(|log| {
+        if log.len() == ACCESS_LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(AccessLogEntry {
+            address: ptr,
+            type_name: type_name::<T>(),
+        });
+    })
+}
+
+/// Get the references that were most recently erased on the current thread using `Erased::new_logged`, oldest first.
+///
+/// At most `ACCESS_LOG_CAPACITY` entries are kept, older entries are discarded.
+///
+/// Example:
+/// ```rust
+/// use erased::{dump_access_log, Erased};
+///
+/// let value = 5u32;
+/// let _erased = Erased::new_logged(&value);
+///
+/// let log = dump_access_log();
+/// assert_eq!(log[0].address.as_ptr(), &value as *const u32 as *mut ());
+/// assert_eq!(log[0].type_name, "u32");
+/// ```
+pub fn dump_access_log() -> Vec<AccessLogEntry> {
+    ACCESS_LOG.with_borrow(|log| log.iter().copied().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::access_log::ACCESS_LOG_CAPACITY;
+    use crate::{dump_access_log, Erased};
+    use std::ptr::NonNull;
+
+    #[test]
+    fn log_test() {
+        let number = 5u32;
+        let greeting = "Hello World";
+        let _erased = [
+            Erased::new_logged(&number),
+            Erased::new(&number),
+            Erased::new_logged(&greeting),
+        ];
+
+        let log = dump_access_log();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].address, NonNull::from(&number).cast());
+        assert_eq!(log[0].type_name, "u32");
+        assert_eq!(log[1].address, NonNull::from(&greeting).cast());
+        assert_eq!(log[1].type_name, "&str");
+    }
+
+    #[test]
+    fn ring_buffer_test() {
+        let values: Vec<u64> = (0..ACCESS_LOG_CAPACITY as u64 + 3).collect();
+        for value in &values {
+            let _ = Erased::new_logged(value);
+        }
+
+        let log = dump_access_log();
+        assert_eq!(log.len(), ACCESS_LOG_CAPACITY);
+        assert_eq!(log[0].address, NonNull::from(&values[3]).cast());
+        assert_eq!(
+            log[ACCESS_LOG_CAPACITY - 1].address,
+            NonNull::from(values.last().unwrap()).cast()
+        );
+    }
+
+    #[test]
+    fn thread_local_test() {
+        let _ = Erased::new_logged(&5u32);
+        std::thread::spawn(|| assert!(dump_access_log().is_empty()))
+            .join()
+            .unwrap();
+        assert_eq!(dump_access_log().len(), 1);
+    }
+}
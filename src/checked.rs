@@ -0,0 +1,43 @@
+use std::any::{type_name, TypeId};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ptr::NonNull;
+
+/// The recorded type of an erased value constructed using `new_checked`.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TypeInfo {
+    pub(crate) id: TypeId,
+    name: &'static str,
+}
+
+impl TypeInfo {
+    pub(crate) fn of<T: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+}
+
+/// Panics if `type_info` is not the type `T`, naming both the recorded and the requested type.
+pub(crate) fn assert_type<T: 'static>(type_info: Option<TypeInfo>) {
+    match type_info {
+        Some(info) if info.id == TypeId::of::<T>() => {}
+        Some(info) => panic!(
+            "Erased value of type `{}` was recovered as type `{}`",
+            info.name,
+            type_name::<T>()
+        ),
+        None => panic!(
+            "Erased value was not created using `new_checked`, but was recovered as type `{}`",
+            type_name::<T>()
+        ),
+    }
+}
+
+/// Hash the recorded `TypeId` of an erased value together with its address.
+pub(crate) fn fingerprint(type_info: Option<TypeInfo>, ptr: NonNull<()>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    type_info.map(|info| info.id).hash(&mut hasher);
+    ptr.hash(&mut hasher);
+    hasher.finish()
+}
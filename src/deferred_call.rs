@@ -0,0 +1,79 @@
+use crate::ErasedBox;
+use std::mem;
+
+/// An erased box together with a function that takes the value in it, which is called when the deferred call is run.
+/// Created using `ErasedBox::into_deferred`.
+///
+/// This can be used to build a queue of deferred operations on values of different types.
+/// If the deferred call is dropped without being run, the erased box is dropped, which leaks the value unless it was created using `ErasedBox::new_owned`.
+///
+/// Example:
+/// ```rust
+/// use erased::{DeferredCall, ErasedBox};
+///
+/// // Safety: The types match the types the boxes were created with.
+/// let calls: Vec<DeferredCall> = unsafe {
+///     vec![
+///         ErasedBox::new(Box::new(5u64)).into_deferred::<u64>(|v| assert_eq!(*v, 5)),
+///         ErasedBox::new(Box::new("Hello")).into_deferred::<&str>(|v| assert_eq!(*v, "Hello")),
+///     ]
+/// };
+/// calls.into_iter().for_each(DeferredCall::run);
+/// ```
+#[derive(Debug)]
+pub struct DeferredCall {
+    erased: ErasedBox,
+    call: unsafe fn(*const (), ErasedBox),
+    f: *const (),
+}
+
+impl DeferredCall {
+    /// Safety: `erased` must have been created from a `T`.
+    pub(crate) unsafe fn new<T>(erased: ErasedBox, f: fn(Box<T>)) -> Self {
+        /// Safety: `f` must be a `fn(Box<T>)` and `erased` must have been created from a `T`.
+        unsafe fn call<T>(f: *const (), erased: ErasedBox) {
+            let f = mem::transmute::<*const (), fn(Box<T>)>(f);
+            f(erased.into_inner::<T>())
+        }
+
+        Self {
+            erased,
+            call: call::<T>,
+            f: f as *const (),
+        }
+    }
+
+    /// Recover the value and pass it to the function of this deferred call
+    pub fn run(self) {
+        // Safety: `call` was created for the type of the value and the function in `DeferredCall::new`.
+        unsafe { (self.call)(self.f, self.erased) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeferredCall, ErasedBox};
+    use std::cell::RefCell;
+
+    #[test]
+    fn run_in_order_test() {
+        thread_local! {
+            static CALLS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        let calls: Vec<DeferredCall> = unsafe {
+            vec![
+                ErasedBox::new(Box::new(5u64)).into_deferred::<u64>(|v| {
+                    CALLS.with_borrow_mut(|calls| calls.push(v.to_string()))
+                }),
+                ErasedBox::new(Box::new(vec![String::from("Hello")])).into_deferred::<Vec<String>>(
+                    |v| CALLS.with_borrow_mut(|calls| calls.extend(*v)),
+                ),
+            ]
+        };
+        assert!(CALLS.with_borrow(|calls| calls.is_empty()));
+
+        calls.into_iter().for_each(DeferredCall::run);
+        assert_eq!(CALLS.with_borrow(|calls| calls.clone()), ["5", "Hello"]);
+    }
+}
@@ -0,0 +1,75 @@
+use std::ptr::NonNull;
+
+/// An erased mutable pointer without a lifetime, created from an `ErasedMut` using `ErasedMut::detach`.
+///
+/// This can be stored in long-lived structures, such as a `static`, where the borrow checker can not track the borrow.
+///
+/// # Warning
+/// This type is **extremely unsafe** to use. Detaching an erased mutable reference removes all lifetime tracking,
+/// so the compiler no longer checks that the value is still alive, or that it is not borrowed elsewhere, when the reference is reattached.
+/// The caller of `DetachedErasedMut::reattach` is entirely responsible for both.
+///
+/// Example:
+/// ```rust
+/// use erased::{DetachedErasedMut, ErasedMut};
+///
+/// let mut value = 5usize;
+/// let detached: DetachedErasedMut = ErasedMut::new(&mut value).detach();
+///
+/// // Safety: `value` is alive and not borrowed elsewhere until `r` is last used, and the type matches.
+/// let r: &mut usize = unsafe { detached.reattach::<usize>() };
+/// *r = 42;
+/// assert_eq!(value, 42);
+/// ```
+#[derive(Debug)]
+pub struct DetachedErasedMut {
+    ptr: NonNull<()>,
+}
+
+impl DetachedErasedMut {
+    pub(crate) fn new(ptr: NonNull<()>) -> Self {
+        Self { ptr }
+    }
+
+    /// Get a mutable reference to `T` back, with a lifetime `'b` chosen by the caller.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create the original `ErasedMut` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// The value must be alive for the entire lifetime `'b`, and must not be accessed through any other reference during `'b`.
+    /// This includes the reference that was used to create the original `ErasedMut`, which must not have been used since the `ErasedMut` was created.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn reattach<'b, T>(self) -> &'b mut T {
+        // Safety: From the safety comment the `T` matches, and the value is alive and not aliased during `'b`.
+        self.ptr.cast::<T>().as_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DetachedErasedMut, ErasedMut};
+
+    struct Registry {
+        pending: Vec<(DetachedErasedMut, u64)>,
+    }
+
+    #[test]
+    fn write_back_test() {
+        let mut a = 1u64;
+        let mut b = 2u64;
+        let mut registry = Registry {
+            pending: vec![
+                (ErasedMut::new(&mut a).detach(), 10),
+                (ErasedMut::new(&mut b).detach(), 20),
+            ],
+        };
+
+        // Safety: `a` and `b` outlive this loop, and are not accessed until it ends.
+        for (detached, value) in registry.pending.drain(..) {
+            *unsafe { detached.reattach::<u64>() } += value;
+        }
+        assert_eq!((a, b), (11, 22));
+    }
+}
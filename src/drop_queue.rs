@@ -0,0 +1,106 @@
+use crate::ErasedBox;
+
+/// A queue of erased boxes of different types, which are dropped together when the queue is flushed.
+///
+/// This can be used to defer the destruction of values, for example until the end of a frame.
+/// Any values that are still queued when the queue is dropped are dropped as well.
+///
+/// Example:
+/// ```rust
+/// use erased::{DropQueue, ErasedBox};
+///
+/// let mut queue = DropQueue::new();
+/// // Safety: The types match the types the boxes were created with.
+/// unsafe {
+///     queue.push::<String>(ErasedBox::new(Box::new(String::from("Hello World"))));
+///     queue.push::<Vec<u32>>(ErasedBox::new(Box::new(vec![1, 2, 3])));
+/// }
+/// assert_eq!(queue.len(), 2);
+///
+/// queue.flush();
+/// assert!(queue.is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct DropQueue {
+    queue: Vec<(ErasedBox, unsafe fn(ErasedBox))>,
+}
+
+impl DropQueue {
+    /// Create a new empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of values in the queue
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check whether the queue contains no values
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Add an erased box to the queue, to be dropped on the next flush.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    /// Additionally, the value must still be valid when the queue is flushed, so any lifetime parameters of `T` must outlive the queue.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn push<T>(&mut self, b: ErasedBox) {
+        // Safety: From the safety comment `b` contains a `T`.
+        self.queue.push((b, |b| unsafe { b.drop_as::<T>() }));
+    }
+
+    /// Drop all values in the queue, in the order they were pushed
+    pub fn flush(&mut self) {
+        for (b, drop) in self.queue.drain(..) {
+            // Safety: `drop` was created for the type of `b` in `DropQueue::push`.
+            unsafe { drop(b) };
+        }
+    }
+}
+
+impl Drop for DropQueue {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DropQueue, ErasedBox};
+    use std::rc::Rc;
+
+    #[test]
+    fn flush_test() {
+        let value = Rc::new(5);
+        let mut queue = DropQueue::new();
+        unsafe {
+            queue.push::<Rc<i32>>(ErasedBox::new(Box::new(value.clone())));
+            queue.push::<(u8, Rc<i32>)>(ErasedBox::new(Box::new((1, value.clone()))));
+            queue.push::<u64>(ErasedBox::new(Box::new(5u64)));
+        }
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        queue.flush();
+        assert!(queue.is_empty());
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        queue.flush();
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn drop_test() {
+        let value = Rc::new(5);
+        let mut queue = DropQueue::new();
+        unsafe { queue.push::<Rc<i32>>(ErasedBox::new(Box::new(value.clone()))) };
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(queue);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Erased;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// An erased reference to a value `&'a T`, which is invalidated when a shared epoch counter advances.
+///
+/// Each reference stores the value of the epoch counter when it was created.
+/// Bumping the shared counter, for example when an arena is reset, invalidates all references that were created before, in one operation.
+///
+/// Example:
+/// ```rust
+/// use erased::EpochErased;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// let epoch = AtomicU64::new(0);
+/// let erased = EpochErased::new(&5u64, &epoch);
+///
+/// // SAFETY: The reference was created from a `u64`
+/// assert_eq!(unsafe { erased.get::<u64>() }, Some(&5));
+///
+/// epoch.fetch_add(1, Ordering::Release);
+/// // SAFETY: The reference was created from a `u64`
+/// assert_eq!(unsafe { erased.get::<u64>() }, None);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct EpochErased<'a> {
+    erased: Erased<'a>,
+    epoch: &'a AtomicU64,
+    created: u64,
+}
+
+impl<'a> EpochErased<'a> {
+    /// Create a new erased reference from a reference to `T`, which is valid until `epoch` advances
+    pub fn new<T>(t: &'a T, epoch: &'a AtomicU64) -> EpochErased<'a> {
+        Self {
+            erased: Erased::new(t),
+            epoch,
+            created: epoch.load(Ordering::Acquire),
+        }
+    }
+
+    /// Check whether the shared epoch has advanced since this erased reference was created
+    pub fn is_stale(&self) -> bool {
+        self.epoch.load(Ordering::Acquire) != self.created
+    }
+
+    /// Get a reference to `T` back from the erased reference, if the shared epoch has not advanced since it was created.
+    /// Returns `None` if the erased reference is stale.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `EpochErased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get<T>(&self) -> Option<&'a T> {
+        // Safety: Guaranteed by the safety comment of this function.
+        (!self.is_stale()).then(|| self.erased.get::<T>())
+    }
+
+    /// Get the value of the shared epoch when this erased reference was created
+    pub fn epoch(&self) -> u64 {
+        self.created
+    }
+
+    /// Get the erased reference without the epoch
+    pub fn erased(&self) -> Erased<'a> {
+        self.erased
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::EpochErased;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn invalidate_test() {
+        let epoch = AtomicU64::new(7);
+        let values = [String::from("Hello"), String::from("World")];
+        let old: Vec<EpochErased> = values.iter().map(|v| EpochErased::new(v, &epoch)).collect();
+        assert!(old.iter().all(|e| e.epoch() == 7 && !e.is_stale()));
+        assert_eq!(unsafe { old[1].get::<String>() }.unwrap(), "World");
+
+        epoch.fetch_add(1, Ordering::Release);
+        assert!(old.iter().all(EpochErased::is_stale));
+        assert!(old.iter().all(|e| unsafe { e.get::<String>() }.is_none()));
+
+        let new = EpochErased::new(&values[0], &epoch);
+        assert_eq!(new.epoch(), 8);
+        assert_eq!(unsafe { new.get::<String>() }.unwrap(), "Hello");
+    }
+}
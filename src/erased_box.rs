@@ -1,11 +1,30 @@
-use std::ptr::NonNull;
+#[cfg(feature = "checked")]
+use crate::checked::TypeInfo;
+use crate::erased_vtable::VtableFns;
+use crate::glue::{Finalized, Glue};
+use crate::{DeferredCall, Erased, ErasedVtable, SafeToErase};
+use std::alloc::{self, Layout};
+use std::any::{self, Any, TypeId};
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::fmt;
+use std::hash::Hasher;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ptr::{self, NonNull};
+use std::sync::mpsc::{SendError, Sender};
+use std::sync::Arc;
+#[cfg(feature = "timed")]
+use std::time::{Duration, Instant};
 
 /// A box with an erased type.
 ///
 /// # Warning
-/// This type **leaks** the Box when it is dropped.
+/// An erased box created using `ErasedBox::new` (or any other constructor except `ErasedBox::new_owned`) **leaks** the Box when it is dropped.
 /// To ensure that the Box is not leaked, call `into_inner` on it before it is dropped.
 ///
+/// An erased box created using `ErasedBox::new_owned` stores a function to drop the value, so it drops the value and frees the allocation when it is dropped.
+///
 /// Example:
 /// ```rust
 /// use erased::ErasedBox;
@@ -20,14 +39,178 @@ use std::ptr::NonNull;
 #[derive(Debug)]
 pub struct ErasedBox {
     ptr: NonNull<()>,
+    layout: Layout,
+    glue: &'static Glue,
+    #[cfg(feature = "checked")]
+    type_info: Option<TypeInfo>,
+    #[cfg(feature = "timed")]
+    created: Instant,
 }
 
 impl ErasedBox {
     /// Create a new erased box from a `Box<T>`
     pub fn new<T>(t: Box<T>) -> ErasedBox {
+        Self::from_parts(
+            NonNull::from(Box::leak(t)).cast(),
+            Layout::new::<T>(),
+            const { &Glue::leaked::<T>() },
+        )
+    }
+
+    /// Create a new erased box that owns the allocation at `ptr` with `layout`, handled by `glue`.
+    fn from_parts(ptr: NonNull<()>, layout: Layout, glue: &'static Glue) -> ErasedBox {
+        Self {
+            ptr,
+            layout,
+            glue,
+            #[cfg(feature = "checked")]
+            type_info: None,
+            #[cfg(feature = "timed")]
+            created: Instant::now(),
+        }
+    }
+
+    /// Create a new erased box from a `Box<T>`, where `T` must be vetted to be erased by implementing `SafeToErase`
+    pub fn new_safe<T: SafeToErase>(t: Box<T>) -> ErasedBox {
+        Self::new(t)
+    }
+
+    /// Create a new erased box from a boxed slice `Box<[T]>`.
+    ///
+    /// The length of the slice is not part of the erased box, so it must be tracked externally to get the slice back using `into_inner_slice`.
+    pub fn new_slice<T>(t: Box<[T]>) -> ErasedBox {
+        let layout = Layout::for_value(&*t);
+        Self::from_parts(
+            NonNull::from(Box::leak(t)).cast(),
+            layout,
+            const { &Glue::leaked::<T>() },
+        )
+    }
+
+    /// Create a new erased box from a `Box<T>`, which drops the value and frees the allocation when the erased box is dropped.
+    ///
+    /// Unlike `ErasedBox::new`, this box is not leaked when it is dropped.
+    /// Recovering the value using `into_inner` still works, in which case the value is not dropped by the erased box.
+    ///
+    /// `T` must be `'static`, since the erased box has no lifetime, so it could be dropped after a value borrowed by `T`:
+    /// ```rust,compile_fail
+    /// use erased::ErasedBox;
+    ///
+    /// let value = String::from("Hello World");
+    /// let erased = ErasedBox::new_owned(Box::new(&value));
+    /// drop(value);
+    /// drop(erased);
+    /// ```
+    pub fn new_owned<T: 'static>(t: Box<T>) -> ErasedBox {
+        // Safety: `T` is `'static`, so the value can be dropped at any time.
+        unsafe { Self::new_owned_unchecked(t) }
+    }
+
+    /// Create a new erased box from a `Box<T>`, which drops the value when the erased box is dropped, without requiring `T: 'static`.
+    ///
+    /// Safety: The value must still be valid whenever the erased box is dropped, so any lifetime parameters of `T` must outlive the erased box.
+    unsafe fn new_owned_unchecked<T>(t: Box<T>) -> ErasedBox {
+        Self::from_parts(
+            NonNull::from(Box::leak(t)).cast(),
+            Layout::new::<T>(),
+            const { &Glue::owned::<T>() },
+        )
+    }
+
+    /// Create a new erased box from a `Box<T>`, with the vtable `vtable` of `T` attached.
+    ///
+    /// Like `ErasedBox::new_owned`, this box drops the value when it is dropped.
+    /// Additionally, the value can be cloned, formatted, compared and hashed through the vtable,
+    /// using `ErasedBox::try_clone`, `ErasedBox::vtable_debug`, `ErasedBox::vtable_eq` and `ErasedBox::vtable_hash`.
+    ///
+    /// # Panics
+    /// Panics if `vtable` was not created for `T` using `ErasedVtable::of`.
+    pub fn with_vtable<T: 'static>(t: Box<T>, vtable: &'static ErasedVtable) -> ErasedBox {
+        assert!(
+            vtable
+                .glue
+                .vtable
+                .as_ref()
+                .is_some_and(|fns| fns.type_id == TypeId::of::<T>()),
+            "Vtable was not created for `{}`",
+            any::type_name::<T>()
+        );
+        Self::from_parts(
+            NonNull::from(Box::leak(t)).cast(),
+            Layout::new::<T>(),
+            &vtable.glue,
+        )
+    }
+
+    /// Create a new erased box from a `Box<T>`, which can be converted into a `Box<dyn Any + Send + Sync>` using `ErasedBox::into_box_any`,
+    /// or into an `Arc<dyn Any + Send + Sync>` using `ErasedBox::into_arc_any`.
+    ///
+    /// Like `ErasedBox::new_owned`, this box drops the value when it is dropped.
+    pub fn new_any_sync<T: Any + Send + Sync>(t: Box<T>) -> ErasedBox {
+        Self::from_parts(
+            NonNull::from(Box::leak(t)).cast(),
+            Layout::new::<T>(),
+            const { &Glue::any_sync::<T>() },
+        )
+    }
+
+    /// Create a new erased box from a `Box<T>`, recording the `TypeId` of `T`.
+    #[cfg(feature = "checked")]
+    pub fn new_checked<T: 'static>(t: Box<T>) -> ErasedBox {
         Self {
-            ptr: NonNull::from(Box::leak(t)).cast(),
+            type_info: Some(TypeInfo::of::<T>()),
+            ..Self::new(t)
+        }
+    }
+
+    /// Create a new erased box from a `Box<T>`, with a finalizer `f` that is called right before the value is returned by `into_inner`.
+    /// The finalizer runs exactly once, and is not called if the box is leaked.
+    ///
+    /// The finalizer is stored in the allocation after the value, so the value is moved into a new allocation.
+    // The value is taken as a `Box<T>` like in the other constructors, even though it is moved out of the box.
+    #[allow(clippy::boxed_local)]
+    pub fn with_finalizer<T>(t: Box<T>, f: fn(&T)) -> ErasedBox {
+        let finalized = Box::new(Finalized {
+            value: *t,
+            finalizer: f,
+        });
+        Self::from_parts(
+            NonNull::from(Box::leak(finalized)).cast(),
+            Layout::new::<Finalized<T>>(),
+            const { &Glue::finalized::<T>() },
+        )
+    }
+
+    /// Create a new erased box for `value`, with an allocation that is aligned to at least `ALIGN` bytes.
+    /// This is useful for SIMD or DMA buffers that need a larger alignment than the natural alignment of `T`.
+    ///
+    /// The over-aligned layout is stored in the box, so it is freed correctly.
+    /// Since a `Box<T>` can't own an over-aligned allocation, `into_inner` moves the value into a new allocation with the natural alignment of `T`.
+    /// Methods that move the value out of the box, such as `drop_as` and `emplace`, support over-aligned boxes as well.
+    pub fn new_aligned<T, const ALIGN: usize>(value: T) -> ErasedBox {
+        const {
+            assert!(ALIGN.is_power_of_two(), "Alignment must be a power of two");
         }
+        let layout = Layout::new::<T>()
+            .align_to(ALIGN)
+            .expect("Over-aligned layout is valid");
+        let ptr = alloc(layout).cast::<T>();
+        // Safety: The pointer was just allocated with a layout that fits a `T`.
+        unsafe { ptr.as_ptr().write(value) };
+
+        Self::from_parts(ptr.cast(), layout, const { &Glue::leaked::<T>() })
+    }
+
+    /// Create a new erased box for a `T`, of which all bytes are zero.
+    ///
+    /// The value is not initialized yet, use `ErasedBox::assume_init` to get the `Box<T>` out of it.
+    pub fn new_zeroed<T>() -> ErasedBox {
+        Self::new(Box::<T>::new_zeroed())
+    }
+
+    /// Create a new erased box from a closure, which can be called using `ErasedBox::call`.
+    pub fn from_closure<F: FnMut() -> R, R>(f: F) -> ErasedBox {
+        Self::new(Box::new(f))
     }
 
     /// Get a normal box `Box<T>` back from the erased box.
@@ -38,8 +221,213 @@ impl ErasedBox {
     ///
     /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
     pub unsafe fn into_inner<T>(self) -> Box<T> {
-        // Safety: From the safety comment the `T` matches the `T` this erased box was created with. The reference is unique since we consume `self`.
-        Box::from_raw(self.ptr.cast::<T>().as_mut())
+        if self.layout != Layout::new::<T>() {
+            // The allocation was not created by a `Box<T>`, for example by `ErasedBox::new_aligned`, so the value is moved into a new box.
+            // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+            return Box::new(self.take::<T>());
+        }
+        // The value is taken out of the box, so it must not be dropped by the drop glue.
+        let this = ManuallyDrop::new(self);
+        this.finalize();
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with, and the allocation has the layout of a `Box<T>`. The reference is unique since we consume `self`.
+        Box::from_raw(this.ptr.cast::<T>().as_mut())
+    }
+
+    /// Move the value out of this box and free the allocation, which may be over-aligned if the box was created using `ErasedBox::new_aligned`.
+    ///
+    /// Safety: The `T` must match the `T` that was used to create this erased box exactly.
+    unsafe fn take<T>(self) -> T {
+        // The value is taken out of the box, so it must not be dropped by the drop glue.
+        let this = ManuallyDrop::new(self);
+        this.finalize();
+        debug_assert!(mem::size_of::<T>() <= this.layout.size());
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with. The value was moved out before freeing the allocation.
+        let value = this.ptr.cast::<T>().read();
+        dealloc(this.ptr, this.layout);
+        value
+    }
+
+    /// Call the finalizer of this box, if it was created using `ErasedBox::with_finalizer`.
+    ///
+    /// Safety: The value must still be valid, and the finalizer must not have been called already.
+    unsafe fn finalize(&self) {
+        if let Some(finalize) = self.glue.finalize {
+            // Safety: The glue was created for the `T` this erased box was created with, and the value is still valid.
+            finalize(self.ptr)
+        }
+    }
+
+    /// Get a boxed slice `Box<[T]>` back from an erased box that was created using `ErasedBox::new_slice`.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the element type `T` that was used to create this erased box in `ErasedBox::new_slice` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// `len` must match the length of the slice that was used to create this erased box.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn into_inner_slice<T>(self, len: usize) -> Box<[T]> {
+        debug_assert_eq!(Layout::array::<T>(len).ok(), Some(self.layout));
+        // The slice is taken out of the box, so it must not be dropped by the drop glue.
+        let this = ManuallyDrop::new(self);
+        // Safety: From the safety comment the element type and length match the slice this erased box was created with. The reference is unique since we consume `self`.
+        Box::from_raw(ptr::slice_from_raw_parts_mut(
+            this.ptr.cast::<T>().as_ptr(),
+            len,
+        ))
+    }
+
+    /// Get a normal box `Box<T>` back from the erased box, together with the time that has passed since the erased box was created.
+    /// This can be used to profile how long erased values persist, for example in a pool.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    #[cfg(feature = "timed")]
+    pub unsafe fn into_inner_timed<T>(self) -> (Box<T>, Duration) {
+        let elapsed = self.created.elapsed();
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        (self.into_inner::<T>(), elapsed)
+    }
+
+    /// Convert this erased box into a `Box<dyn Any + Send + Sync>`, which can be downcast to the type of the value.
+    /// The value is not moved, the allocation of this box is reused.
+    ///
+    /// # Panics
+    /// Panics if this box was not created using `ErasedBox::new_any_sync`.
+    pub fn into_box_any(self) -> Box<dyn Any + Send + Sync> {
+        let any = self.glue.any.expect(
+            "Only erased boxes created using `new_any_sync` can be converted into a `dyn Any`",
+        );
+        // The box is taken over by the `Box<dyn Any>`, so it must not be dropped by the drop glue.
+        let this = ManuallyDrop::new(self);
+        // Safety: The glue was created for the `T` this erased box was created with in `ErasedBox::new_any_sync`, and the value is still valid.
+        unsafe { any(this.ptr) }
+    }
+
+    /// Convert this erased box into an `Arc<dyn Any + Send + Sync>`, which can be shared between threads and downcast to the type of the value.
+    ///
+    /// # Panics
+    /// Panics if this box was not created using `ErasedBox::new_any_sync`.
+    pub fn into_arc_any(self) -> Arc<dyn Any + Send + Sync> {
+        Arc::from(self.into_box_any())
+    }
+
+    /// Get a normal box `Box<T>` back from an erased box that was created using `ErasedBox::new_zeroed`.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new_zeroed` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// The value must be initialized, so either all zeroes must be a valid value of `T`, or the value must have been initialized since the creation of the box.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn assume_init<T>(self) -> Box<T> {
+        // Safety: From the safety comment this box was created from a `Box<MaybeUninit<T>>`, which is initialized.
+        self.into_inner::<MaybeUninit<T>>().assume_init()
+    }
+
+    /// Initialize the value in an erased box that was created using `ErasedBox::new_zeroed`, using the fallible initializer `f`.
+    ///
+    /// If `f` returns `Ok`, the value is initialized and can be recovered using `ErasedBox::assume_init`.
+    /// If `f` returns `Err` or panics, the value is left uninitialized, and nothing is dropped, so a half-built value is never dropped.
+    /// Any resources owned by a half-built value are leaked, unless `f` cleans them up before returning `Err`.
+    /// The box can then be initialized again, or freed using `into_inner::<MaybeUninit<T>>`.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new_zeroed` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// If `f` returns `Ok`, it must have fully initialized the value.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn init_or_cleanup<T, E>(
+        &mut self,
+        f: impl FnOnce(&mut MaybeUninit<T>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        // Safety: From the safety comment this box was created from a `Box<MaybeUninit<T>>`, which never drops its contents.
+        f(self.get_mut::<MaybeUninit<T>>())
+    }
+
+    /// Drop the value of type `T` in this erased box, and free the allocation.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn drop_as<T>(self) {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        drop(self.take::<T>())
+    }
+
+    /// Send the value of type `T` in this erased box over the channel `tx`, and free the allocation.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn send_as<T: Send>(self, tx: &Sender<T>) -> Result<(), SendError<T>> {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        tx.send(self.take::<T>())
+    }
+
+    /// Move the value of type `T` in this erased box into `slot`, for example on the stack, and free the allocation.
+    ///
+    /// Any previous value in `slot` is overwritten without being dropped.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn emplace<T>(self, slot: &mut MaybeUninit<T>) {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        slot.write(self.take::<T>());
+    }
+
+    /// Move the value of type `T` in this erased box into a new box allocated in `alloc`, and free the original allocation.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    #[cfg(feature = "allocator_api")]
+    pub unsafe fn rebox_in<T, A: alloc::Allocator>(self, alloc: A) -> Box<T, A> {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        Box::new_in(self.take::<T>(), alloc)
+    }
+
+    /// Package this erased box together with the function `f`, which is called with the value when the returned `DeferredCall` is run.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn into_deferred<T>(self, f: fn(Box<T>)) -> DeferredCall {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        DeferredCall::new::<T>(self, f)
+    }
+
+    /// Move the value of type `T` in this erased box into the arena `arena`, and free the original allocation.
+    ///
+    /// The arena does not run the destructor of the value when it is dropped.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    #[cfg(feature = "bumpalo")]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn move_into_arena<T>(self, arena: &bumpalo::Bump) -> &mut T {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with.
+        arena.alloc(self.take::<T>())
     }
 
     /// Get a reference to the value in this box.
@@ -66,33 +454,1512 @@ impl ErasedBox {
         // Self is borrowed mutably ensuring exclusive access.
         self.ptr.cast::<T>().as_mut()
     }
-}
 
-impl<T> From<Box<T>> for ErasedBox {
-    fn from(value: Box<T>) -> Self {
-        Self::new(value)
+    /// Call `visitor` with a mutable reference to the value in this box.
+    /// The reference can't escape the visitor, so the value can only be mutated for the duration of the call.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn visit_mut<T>(&mut self, visitor: &mut dyn FnMut(&mut T)) {
+        // Safety: Guaranteed by the safety comment of this function.
+        visitor(self.get_mut::<T>())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::ErasedBox;
+    /// Get an erased reference to the value in this box, which borrows the box.
+    ///
+    /// # Lifetimes
+    /// The lifetime `'a` of the returned `Erased<'a>` is the borrow of the box, so the compiler checks that the erased reference does not outlive the box:
+    /// ```rust,compile_fail
+    /// use erased::ErasedBox;
+    ///
+    /// let boxed = ErasedBox::new(Box::new(5u32));
+    /// let erased = boxed.as_erased();
+    /// // Safety: Matches the type the box was created with
+    /// unsafe { boxed.drop_as::<u32>() };
+    /// assert_eq!(unsafe { *erased.get::<u32>() }, 5);
+    /// ```
+    ///
+    /// References recovered using `Erased::get_with_lifetime` are bounded by `'a` as well, so they can not be widened to a longer lifetime than the borrow of the box:
+    /// ```rust,compile_fail
+    /// use erased::ErasedBox;
+    ///
+    /// fn widen(boxed: &ErasedBox) -> &'static u32 {
+    ///     // Safety: Matches the type the box was created with
+    ///     unsafe { boxed.as_erased().get_with_lifetime::<'static, u32>() }
+    /// }
+    /// ```
+    ///
+    /// If the box holds a `Foo<'x>`, the lifetime `'x` is not part of the type of the box, so the compiler can **not** relate `'a` and `'x`.
+    /// Recovering the value as `Foo<'long>` instead of `Foo<'x>` compiles, but is unsound, like for `Erased::get`.
+    /// When the value is recovered as exactly `Foo<'x>`, the returned reference `&'a Foo<'x>` is valid, since a box holding a `Foo<'x>` may only be used while `'x` is alive.
+    pub fn as_erased(&self) -> Erased<'_> {
+        Erased {
+            ptr: self.ptr,
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: self.type_info,
+            #[cfg(feature = "checked")]
+            is_static: false,
+        }
+    }
 
-    #[test]
-    fn basic_test() {
-        let erased = ErasedBox::new(Box::new(5usize));
-        let r2 = unsafe { erased.into_inner::<usize>() };
-        assert_eq!(*r2, 5);
+    /// Get a reference to the value in a box that was created using `ErasedBox::new_checked`.
+    ///
+    /// # Panics
+    /// Panics if the box was not created from a `T` using `ErasedBox::new_checked`, naming both the recorded and the requested type.
+    #[cfg(feature = "checked")]
+    pub fn get_checked<T: 'static>(&self) -> &T {
+        crate::checked::assert_type::<T>(self.type_info);
+        // Safety: The type id matches, so the value is a `T`.
+        unsafe { self.get_ref::<T>() }
     }
 
-    #[test]
-    fn ref_test() {
-        let mut erased = ErasedBox::new(Box::new(5usize));
-        assert_eq!(*unsafe { erased.get_ref::<usize>() }, 5);
-        *unsafe { erased.get_mut::<usize>() } = 42;
-        assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
+    /// Call the closure in this box.
+    ///
+    /// # Safety
+    /// The generic argument `F` of this function must match the `F` that was used to create this erased box in `ErasedBox::from_closure` exactly.
+    ///
+    /// Since closure types can't be named, `F` can only be provided through inference, for example using a generic helper function.
+    pub unsafe fn call<F: FnMut() -> R, R>(&mut self) -> R {
+        // Safety: Guaranteed by the safety comment of this function.
+        (self.get_mut::<F>())()
+    }
 
-        // Drop `erased`
-        unsafe { erased.into_inner::<usize>() };
+    /// Apply a state transition to the value in this box, turning a `From` into a `To`, and erase the result again.
+    /// This can be used to advance typestate machines that are stored erased.
+    ///
+    /// # Safety
+    /// The generic argument `From` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `From` match.
+    ///
+    /// If this box drops its value, as with `ErasedBox::new_owned`, the returned box drops its value as well, so any lifetime parameters of `To` must outlive the returned box.
+    ///
+    /// It is **strongly recommended** to provide `From` explicitly, even if it can be inferred. This is to make sure that the value of `From` is not accidentally changed.
+    pub unsafe fn transition<From, To>(self, f: impl FnOnce(From) -> To) -> ErasedBox {
+        // Safety: Guaranteed by the safety comment of this function.
+        let owned = self.glue.drop.is_some();
+        let from = self.take::<From>();
+        let to = Box::new(f(from));
+        if owned {
+            // Safety: Guaranteed by the safety comment of this function.
+            ErasedBox::new_owned_unchecked(to)
+        } else {
+            ErasedBox::new(to)
+        }
+    }
+
+    /// Get the value `T` back from this box, together with a clone of the value in a new erased box.
+    /// The clone is owned, as with `ErasedBox::new_owned`, if this box was.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn split_clone<T: Clone>(self) -> (Box<T>, ErasedBox) {
+        // Safety: Guaranteed by the safety comment of this function.
+        let clone = Box::new(self.get_ref::<T>().clone());
+        let clone = if self.glue.drop.is_some() {
+            // Safety: This box drops its value, so it was created from a `T: 'static`.
+            ErasedBox::new_owned_unchecked(clone)
+        } else {
+            ErasedBox::new(clone)
+        };
+        // Safety: Guaranteed by the safety comment of this function.
+        (self.into_inner::<T>(), clone)
+    }
+
+    /// Get scoped mutable access to the value in this box through the fallible operation `f`.
+    ///
+    /// The box keeps ownership of the value, so it is still valid and usable after `f` returns, both on success and on error.
+    /// If `f` fails halfway through an update, it is the responsibility of `f` to leave the value in a state that is acceptable to the caller.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn scope_owned<T, R, E>(
+        &mut self,
+        f: impl FnOnce(&mut T) -> Result<R, E>,
+    ) -> Result<R, E> {
+        // Safety: Guaranteed by the safety comment of this function.
+        f(self.get_mut::<T>())
+    }
+
+    /// Split the value in this box into a header `H` and a payload `P`.
+    ///
+    /// Both the header and the payload are moved into new allocations of their own, so they can be owned independently.
+    /// The original allocation is freed afterwards, without running any destructors, since its contents were moved out.
+    /// The returned payload box has the layout of `P`, and drops its value if this box was created using `ErasedBox::new_owned`.
+    ///
+    /// The payload type `P` is needed, even though the payload is returned erased.
+    /// The offset of the payload depends on the alignment of `P`, since there may be padding after the header.
+    /// It also gives the payload box the layout of `P` and, for owned boxes, the drop glue of `P`, so it can be recovered using `into_inner::<P>` and is dropped correctly.
+    /// Without it, the payload box could only be given an approximate layout and could not drop its value.
+    ///
+    /// # Safety
+    /// The type that was used to create this erased box in `ErasedBox::new` must be a `#[repr(C)]` struct that consists of exactly a field of type `H` followed by a field of type `P`.
+    /// Pay specific attention that any lifetime parameters of `H` and `P` match.
+    ///
+    /// It is **strongly recommended** to provide `H` and `P` explicitly, even if they can be inferred. This is to make sure that their values are not accidentally changed.
+    pub unsafe fn split_header<H, P>(self) -> (Box<H>, ErasedBox) {
+        let (layout, payload_offset) = Layout::new::<H>()
+            .extend(Layout::new::<P>())
+            .expect("Layout of a `#[repr(C)]` struct is valid");
+        debug_assert_eq!(layout.pad_to_align(), self.layout);
+
+        let base = self.ptr.cast::<u8>();
+        // Safety: From the safety comment the allocation contains a `H` at offset `0` and a `P` at `payload_offset`.
+        let header = base.cast::<H>().read();
+        let payload = Box::new(base.add(payload_offset).cast::<P>().read());
+        let owned = self.glue.drop.is_some();
+        // Safety: The header and payload were moved out, so the allocation can be freed without dropping its contents.
+        self.dealloc();
+
+        let payload = if owned {
+            // Safety: This box dropped its value, so it was created from a `'static` type, of which `P` is a field.
+            ErasedBox::new_owned_unchecked(payload)
+        } else {
+            ErasedBox::new(payload)
+        };
+        (Box::new(header), payload)
+    }
+
+    /// Split the value in this box into a descriptor `D` at the front, and a payload `P` after it.
+    ///
+    /// This is `ErasedBox::split_header`, but returns the descriptor by value instead of in a box.
+    /// The payload box has the layout of `P`, and drops its value if this box was created using `ErasedBox::new_owned`.
+    ///
+    /// # Safety
+    /// The type that was used to create this erased box in `ErasedBox::new` must be a `#[repr(C)]` struct that consists of exactly a field of type `D` followed by a field of type `P`.
+    /// Pay specific attention that any lifetime parameters of `D` and `P` match.
+    ///
+    /// It is **strongly recommended** to provide `D` and `P` explicitly, even if they can be inferred. This is to make sure that their values are not accidentally changed.
+    pub unsafe fn into_descriptor_and_payload<D, P>(self) -> (D, ErasedBox) {
+        // Safety: Guaranteed by the safety comment of this function.
+        let (descriptor, payload) = self.split_header::<D, P>();
+        (*descriptor, payload)
+    }
+
+    /// Move the value in this box to the allocation at `dst`, and return a new erased box that owns `dst`.
+    ///
+    /// The value is moved bytewise, without dropping or recreating it, after which the original allocation is freed.
+    /// This can be used to move erased values during heap compaction.
+    ///
+    /// # Panics
+    /// Panics if `dst` is not aligned to the alignment of this box.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// `dst` must be valid for writes of the layout of this box, and must not overlap with this box.
+    /// Ownership of the allocation at `dst` is transferred to the returned box, so it must have been allocated using the global allocator with the layout of this box.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn relocate<T>(self, dst: NonNull<u8>) -> ErasedBox {
+        debug_assert!(size_of::<T>() <= self.layout.size());
+        assert!(
+            dst.as_ptr().addr().is_multiple_of(self.layout.align()),
+            "Destination is not aligned"
+        );
+
+        // The whole allocation is moved, since it may store more than the `T`, such as the finalizer of `ErasedBox::with_finalizer`.
+        // Safety: From the safety comment `dst` is valid for writes of the layout of this box and doesn't overlap.
+        ptr::copy_nonoverlapping(
+            self.ptr.cast::<u8>().as_ptr(),
+            dst.as_ptr(),
+            self.layout.size(),
+        );
+        let relocated = ErasedBox {
+            ptr: dst.cast(),
+            ..self
+        };
+        // Safety: The value was moved to `dst`, so the allocation can be freed without dropping its contents.
+        self.dealloc();
+        relocated
+    }
+
+    /// Check whether this erased box was created from a `T` using `ErasedBox::new_checked`.
+    #[cfg(feature = "checked")]
+    pub fn is<T: 'static>(&self) -> bool {
+        self.matches_type_id(TypeId::of::<T>())
+    }
+
+    /// Get a normal box `Box<T>` back from the erased box, if it was created from a `T` using `ErasedBox::new_checked`.
+    /// Otherwise, the allocation of this box is freed and the fallback box created by `f` is returned.
+    ///
+    /// In that case, the value is dropped if this box was created using `ErasedBox::new_owned`.
+    /// Otherwise the type of the value is not known, so its destructor is not run, as if it was passed to `std::mem::forget`.
+    #[cfg(feature = "checked")]
+    pub fn into_inner_or_else<T: 'static>(self, f: impl FnOnce() -> Box<T>) -> Box<T> {
+        if self.is::<T>() {
+            // Safety: The type id matches, so the value is a `T`.
+            unsafe { self.into_inner::<T>() }
+        } else if self.glue.drop.is_some() {
+            drop(self);
+            f()
+        } else {
+            // Safety: The value is forgotten, which is always allowed.
+            unsafe { self.dealloc() };
+            f()
+        }
+    }
+
+    /// Check whether this erased box was created using `ErasedBox::new_checked` from a type with the `TypeId` `id`.
+    /// This can be used when the expected type is only known at runtime.
+    #[cfg(feature = "checked")]
+    pub fn matches_type_id(&self, id: TypeId) -> bool {
+        self.type_info.is_some_and(|info| info.id == id)
+    }
+
+    /// Get the `TypeId` of the value in this erased box, if it was created using `ErasedBox::new_checked`.
+    #[cfg(feature = "checked")]
+    pub fn type_id(&self) -> Option<TypeId> {
+        self.type_info.map(|info| info.id)
+    }
+
+    /// Compute a fingerprint of this erased box, combining the `TypeId` recorded by `ErasedBox::new_checked` with the address of the value.
+    /// This can be used as a compact key for memoization tables, keyed on a specific value of a specific type.
+    ///
+    /// Note that addresses are reused after a value is freed, so a new value may have the same fingerprint as an old value that has since been freed.
+    #[cfg(feature = "checked")]
+    pub fn fingerprint(&self) -> u64 {
+        crate::checked::fingerprint(self.type_info, self.ptr)
+    }
+
+    /// Get the layout of the allocation of this box.
+    /// This is the layout of the value, unless the box was created using `ErasedBox::new_aligned` or `ErasedBox::with_finalizer`.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Get the size of the value of type `T` in this box.
+    ///
+    /// In debug builds, this asserts that the size of `T` matches the size of the layout of this box, unless the allocation also stores a finalizer.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn size_as<T>(&self) -> usize {
+        debug_assert!(
+            mem::size_of::<T>() == self.layout.size() || self.glue.finalize.is_some(),
+            "Size of `T` does not match the size of the erased value"
+        );
+        mem::size_of::<T>()
+    }
+
+    /// Read the bytes of the value in this box as the union `U`, for example to view a float as its bits.
+    ///
+    /// In debug builds, this asserts that `U` is not larger than the value in this box.
+    ///
+    /// # Safety
+    /// `U` must be a `#[repr(C)]` union, which is not larger than the value in this box.
+    /// The bytes of the value in this box must be initialized, and be valid for at least one of the fields of `U`.
+    pub unsafe fn read_union<U: Copy>(&self) -> U {
+        debug_assert!(
+            mem::size_of::<U>() <= self.layout.size(),
+            "Size of `U` is larger than the size of the erased value"
+        );
+        // Safety: From the safety comment `U` fits in the value in this box, and the bytes are valid for `U`. The read is unaligned, so the alignment of `U` doesn't matter.
+        self.ptr.cast::<U>().read_unaligned()
+    }
+
+    /// Check whether the value in this box needs to be dropped, see `std::mem::needs_drop`.
+    /// If this returns `false`, the value may be freed without running its destructor.
+    pub fn needs_drop(&self) -> bool {
+        self.glue.needs_drop
+    }
+
+    /// Get the pointer to the value in this box as a `*mut c_void`, for passing it to C APIs as a `void*` user-data pointer.
+    /// The pointer is valid until the box is freed, and may be used for writes since this box is borrowed mutably.
+    pub fn as_mut_c_void(&mut self) -> *mut c_void {
+        self.ptr.as_ptr().cast()
+    }
+
+    /// Convert this box into a `*mut c_void` that owns the value, for passing ownership of it to C APIs.
+    /// Use `ErasedBox::from_c_void` to take back ownership of the value, otherwise it is leaked.
+    ///
+    /// Any finalizer or drop glue of this box is discarded.
+    pub fn into_c_void(self) -> *mut c_void {
+        ManuallyDrop::new(self).ptr.as_ptr().cast()
+    }
+
+    /// Take back ownership of a value from a `*mut c_void`, as passed back by a C callback.
+    ///
+    /// # Safety
+    /// `ptr` must have been created using `ErasedBox::into_c_void` from a box that was created from a `Box<T>` (so not using `ErasedBox::new_aligned` or `ErasedBox::with_finalizer`), and ownership of the value may not have been taken back already.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn from_c_void<T>(ptr: *mut c_void) -> ErasedBox {
+        // Safety: From the safety comment the pointer is owned and points to a value allocated by a `Box<T>`.
+        Self::new(Box::from_raw(ptr.cast::<T>()))
+    }
+
+    /// Get the pointer to the value in this box.
+    pub(crate) fn ptr(&self) -> NonNull<()> {
+        self.ptr
+    }
+
+    /// Clone the value in this box through the vtable of the box, into a new erased box with the same vtable.
+    /// Returns `None` if this box was not created using `ErasedBox::with_vtable`.
+    pub fn try_clone(&self) -> Option<ErasedBox> {
+        let vtable = self.glue.vtable.as_ref()?;
+        Some(Self {
+            // Safety: The vtable was created for the `T` this erased box was created with, and the value is still valid.
+            ptr: unsafe { (vtable.clone)(self.ptr) },
+            #[cfg(feature = "timed")]
+            created: Instant::now(),
+            ..*self
+        })
+    }
+
+    /// Format the value in this box through the vtable of the box.
+    /// Returns `None` if this box was not created using `ErasedBox::with_vtable`.
+    pub fn vtable_debug(&self) -> Option<impl fmt::Debug + '_> {
+        Some(VtableDebug {
+            erased: self,
+            vtable: self.glue.vtable.as_ref()?,
+        })
+    }
+
+    /// Compare the values in both boxes through their vtables.
+    /// Values of different types are never equal.
+    /// Returns `None` if either box was not created using `ErasedBox::with_vtable`.
+    pub fn vtable_eq(&self, other: &ErasedBox) -> Option<bool> {
+        let (a, b) = (self.glue.vtable.as_ref()?, other.glue.vtable.as_ref()?);
+        if a.type_id != b.type_id {
+            return Some(false);
+        }
+        // Safety: Both vtables were created for the same `T`, which both erased boxes were created with.
+        Some(unsafe { (a.eq)(self.ptr, other.ptr) })
+    }
+
+    /// Feed the value in this box into `state` through the vtable of the box.
+    /// Returns `false` without hashing anything if this box was not created using `ErasedBox::with_vtable`.
+    pub fn vtable_hash<H: Hasher>(&self, state: &mut H) -> bool {
+        let Some(vtable) = &self.glue.vtable else {
+            return false;
+        };
+        // Safety: The vtable was created for the `T` this erased box was created with, and the value is still valid.
+        unsafe { (vtable.hash)(self.ptr, state) };
+        true
+    }
+
+    /// Free the allocation of this box, without dropping the value in it.
+    ///
+    /// # Safety
+    /// The value in this box must have been moved out or must not need to be dropped.
+    unsafe fn dealloc(self) {
+        let this = ManuallyDrop::new(self);
+        dealloc(this.ptr, this.layout)
+    }
+
+    /// Set the value of the `Cell<T>` in this box, through a shared reference.
+    ///
+    /// # Safety
+    /// The erased box must have been created in `ErasedBox::new` from a `Box<Cell<T>>`, where `T` matches the generic argument of this function exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn cell_set<T>(&self, v: T) {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.get_ref::<Cell<T>>().set(v)
+    }
+
+    /// Get a copy of the value of the `Cell<T>` in this box, through a shared reference.
+    ///
+    /// # Safety
+    /// The erased box must have been created in `ErasedBox::new` from a `Box<Cell<T>>`, where `T` matches the generic argument of this function exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn cell_get<T: Copy>(&self) -> T {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.get_ref::<Cell<T>>().get()
+    }
+
+    /// Replace the value in this box with the result of `f`, which is given the old value by value.
+    /// The new value is written back into the same allocation.
+    ///
+    /// If `f` panics, the old value has already been moved into `f` and is dropped while unwinding.
+    /// In that case the allocation is freed, and this erased box is left holding a `()` instead, so the old value can't be recovered anymore.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn replace_with<T>(&mut self, f: impl FnOnce(T) -> T) {
+        /// Frees the allocation without dropping its contents if `f` panics, and leaves a `()` in the box instead.
+        struct EmptyOnUnwind<'b>(&'b mut ErasedBox);
+
+        impl Drop for EmptyOnUnwind<'_> {
+            fn drop(&mut self) {
+                let old = mem::replace(self.0, ErasedBox::new(Box::new(())));
+                // Safety: The value in the allocation was moved out, so it can be freed without dropping its contents.
+                unsafe { old.dealloc() };
+            }
+        }
+
+        let ptr = self.ptr.cast::<T>();
+        let guard = EmptyOnUnwind(self);
+        // Safety: From the safety comment the `T` matches the `T` this box was created with. Self is borrowed mutably ensuring exclusive access.
+        let new = f(ptr.as_ptr().read());
+        mem::forget(guard);
+        // Safety: The old value was moved out above, so the slot is overwritten without dropping it.
+        ptr.as_ptr().write(new);
+    }
+
+    /// Fold over the values in `boxes`, which must all be a `T`, into an accumulator.
+    ///
+    /// Example:
+    /// ```rust
+    /// use erased::ErasedBox;
+    ///
+    /// let boxes: Vec<ErasedBox> = (1u32..=3).map(|v| ErasedBox::new_owned(Box::new(v))).collect();
+    /// /// Safety: All boxes were created from a `u32`.
+    /// let total = unsafe { ErasedBox::fold_as::<u32, u32>(&boxes, 0, |acc, v| acc + v) };
+    /// assert_eq!(total, 6);
+    /// ```
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create **each** of the erased boxes in `boxes` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn fold_as<T, Acc>(
+        boxes: &[ErasedBox],
+        init: Acc,
+        mut f: impl FnMut(Acc, &T) -> Acc,
+    ) -> Acc {
+        boxes
+            .iter()
+            // Safety: From the safety comment every box was created from a `T`.
+            .fold(init, |acc, erased| f(acc, erased.get_ref::<T>()))
+    }
+}
+
+/// Iterate over the `TypeId`s of the values in `items`, for example to see which types a heterogeneous collection contains before processing it.
+/// Boxes that were not created using `ErasedBox::new_checked` have no recorded `TypeId`, so they are skipped.
+#[cfg(feature = "checked")]
+pub fn type_tags(items: &[ErasedBox]) -> impl Iterator<Item = TypeId> + '_ {
+    items.iter().filter_map(ErasedBox::type_id)
+}
+
+/// Convert every erased box in `items` into a `Box<dyn Any>`, for passing a collection of erased values of different types to `Any`-based APIs.
+///
+/// # Panics
+/// Panics if any of the boxes was not created using `ErasedBox::new_any_sync`.
+pub fn into_any_vec(items: Vec<ErasedBox>) -> Vec<Box<dyn Any>> {
+    items
+        .into_iter()
+        .map(|erased| erased.into_box_any() as Box<dyn Any>)
+        .collect()
+}
+
+/// Drop the values in every erased box in `boxes` and free their allocations, for destroying a collection of erased values of different types.
+///
+/// # Panics
+/// Panics if any of the boxes has no drop glue, because it was not created using `ErasedBox::new_owned` or `ErasedBox::with_vtable`.
+pub fn drop_all(boxes: Vec<ErasedBox>) {
+    assert!(
+        boxes.iter().all(|erased| erased.glue.drop.is_some()),
+        "Only erased boxes with drop glue can be dropped using `drop_all`"
+    );
+    drop(boxes)
+}
+
+/// Allocate uninitialized memory for `layout`, in the way that `dealloc` expects.
+/// If the size of `layout` is zero, no memory is allocated and a dangling pointer with the alignment of `layout` is returned.
+fn alloc(layout: Layout) -> NonNull<()> {
+    if layout.size() == 0 {
+        NonNull::new(ptr::without_provenance_mut(layout.align())).expect("Alignment is not zero")
+    } else {
+        // Safety: The size of the layout is not zero.
+        let ptr = unsafe { alloc::alloc(layout) }.cast::<()>();
+        NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout))
+    }
+}
+
+/// Free the allocation at `ptr` with `layout`, without dropping the value in it.
+///
+/// # Safety
+/// The allocation must have been created by an `ErasedBox` with `layout`, and the value in it must have been moved out or must not need to be dropped.
+unsafe fn dealloc(ptr: NonNull<()>, layout: Layout) {
+    if layout.size() != 0 {
+        // Safety: The allocation was created using the global allocator with `layout`.
+        alloc::dealloc(ptr.as_ptr().cast(), layout);
+    }
+}
+
+impl Drop for ErasedBox {
+    fn drop(&mut self) {
+        if let Some(drop) = self.glue.drop {
+            // Safety: The glue was created for the `T` this erased box was created with in `ErasedBox::new_owned`, and the value is still valid.
+            unsafe { drop(self.ptr) }
+        }
+    }
+}
+
+/// Formats the value in an erased box through its vtable, created using `ErasedBox::vtable_debug`.
+struct VtableDebug<'b> {
+    erased: &'b ErasedBox,
+    vtable: &'static VtableFns,
+}
+
+impl fmt::Debug for VtableDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Safety: The vtable was created for the `T` the erased box was created with, and the value is still valid.
+        unsafe { (self.vtable.debug)(self.erased.ptr, f) }
+    }
+}
+
+impl<T> From<Box<T>> for ErasedBox {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::erased_box::{drop_all, into_any_vec};
+    use crate::{ErasedBox, ErasedVtable};
+    use std::alloc::{self, Layout};
+    #[cfg(feature = "checked")]
+    use std::any::TypeId;
+    use std::cell::{Cell, RefCell};
+    use std::ffi::c_void;
+    use std::hash::{DefaultHasher, Hash, Hasher};
+    use std::mem::MaybeUninit;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::ptr::NonNull;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn basic_test() {
+        let erased = ErasedBox::new(Box::new(5usize));
+        let r2 = unsafe { erased.into_inner::<usize>() };
+        assert_eq!(*r2, 5);
+    }
+
+    #[test]
+    fn ref_test() {
+        let mut erased = ErasedBox::new(Box::new(5usize));
+        assert_eq!(*unsafe { erased.get_ref::<usize>() }, 5);
+        *unsafe { erased.get_mut::<usize>() } = 42;
+        assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
+
+        // Drop `erased`
+        unsafe { erased.into_inner::<usize>() };
+    }
+
+    #[test]
+    fn replace_with_test() {
+        let mut erased = ErasedBox::new(Box::new(String::from("Hello")));
+        unsafe { erased.replace_with::<String>(|s| s + " World") };
+        assert_eq!(*unsafe { erased.into_inner::<String>() }, "Hello World");
+    }
+
+    #[test]
+    fn replace_with_panic_test() {
+        struct DropCounter<'a>(&'a Cell<usize>);
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut erased = ErasedBox::new(Box::new(DropCounter(&drops)));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            erased.replace_with::<DropCounter>(|_| panic!("replacement failed"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 1);
+
+        // The freed allocation is no longer reachable, the box holds a `()` instead.
+        assert_eq!(erased.layout(), Layout::new::<()>());
+        assert!(!erased.needs_drop());
+        let () = *unsafe { erased.into_inner::<()>() };
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn zeroed_test() {
+        #[derive(Debug, PartialEq)]
+        struct Zeroable {
+            a: u64,
+            b: [u8; 3],
+            c: Option<&'static u32>,
+        }
+
+        let erased = ErasedBox::new_zeroed::<Zeroable>();
+        let v = unsafe { erased.assume_init::<Zeroable>() };
+        assert_eq!(
+            *v,
+            Zeroable {
+                a: 0,
+                b: [0; 3],
+                c: None
+            }
+        );
+    }
+
+    #[test]
+    fn transition_test() {
+        struct Open {
+            name: String,
+        }
+        struct Closed {
+            name: String,
+            bytes: usize,
+        }
+
+        let erased = ErasedBox::new(Box::new(Open {
+            name: String::from("file"),
+        }));
+        let erased = unsafe {
+            erased.transition::<Open, Closed>(|open| Closed {
+                name: open.name,
+                bytes: 42,
+            })
+        };
+
+        let closed = unsafe { erased.into_inner::<Closed>() };
+        assert_eq!(closed.name, "file");
+        assert_eq!(closed.bytes, 42);
+    }
+
+    #[test]
+    fn visit_mut_test() {
+        let mut erased = ErasedBox::new_owned(Box::new(vec![1u32, 2]));
+        let mut visits = 0;
+        let mut visitor = |v: &mut Vec<u32>| {
+            v.push(3);
+            visits += 1;
+        };
+        unsafe { erased.visit_mut::<Vec<u32>>(&mut visitor) };
+        unsafe { erased.visit_mut::<Vec<u32>>(&mut visitor) };
+        assert_eq!(visits, 2);
+        assert_eq!(unsafe { erased.get_ref::<Vec<u32>>() }, &[1, 2, 3, 3]);
+
+        let mut visitors: [fn(&mut Vec<u32>); 2] = [|v| v.clear(), |v| v.push(42)];
+        for visitor in &mut visitors {
+            unsafe { erased.visit_mut::<Vec<u32>>(visitor) };
+        }
+        assert_eq!(unsafe { erased.get_ref::<Vec<u32>>() }, &[42]);
+    }
+
+    #[test]
+    fn scope_owned_test() {
+        let mut erased = ErasedBox::new(Box::new(vec![1u32, 2]));
+
+        let ok = unsafe {
+            erased.scope_owned::<Vec<u32>, _, ()>(|v| {
+                v.push(3);
+                Ok(v.len())
+            })
+        };
+        assert_eq!(ok, Ok(3));
+
+        let err = unsafe {
+            erased.scope_owned::<Vec<u32>, (), _>(|v| {
+                v.pop();
+                Err("failed")
+            })
+        };
+        assert_eq!(err, Err("failed"));
+
+        assert_eq!(*unsafe { erased.into_inner::<Vec<u32>>() }, [1, 2]);
+    }
+
+    #[test]
+    fn closure_test() {
+        /// An erased closure together with the functions to call and drop it, so `F` is inferred correctly.
+        struct Callback<R> {
+            erased: ErasedBox,
+            call: unsafe fn(&mut ErasedBox) -> R,
+            drop: unsafe fn(ErasedBox),
+        }
+
+        fn erase<F: FnMut() -> R, R>(f: F) -> Callback<R> {
+            Callback {
+                erased: ErasedBox::from_closure(f),
+                call: ErasedBox::call::<F, R>,
+                drop: |erased| drop(unsafe { erased.into_inner::<F>() }),
+            }
+        }
+
+        let mut counter = 0u32;
+        let mut count = erase(move || {
+            counter += 1;
+            counter
+        });
+        let name = String::from("Hello");
+        let mut greet = erase(move || format!("{name} World"));
+
+        assert_eq!(unsafe { (count.call)(&mut count.erased) }, 1);
+        assert_eq!(unsafe { (count.call)(&mut count.erased) }, 2);
+        assert_eq!(unsafe { (greet.call)(&mut greet.erased) }, "Hello World");
+
+        unsafe { (count.drop)(count.erased) };
+        unsafe { (greet.drop)(greet.erased) };
+    }
+
+    #[test]
+    fn split_header_test() {
+        #[repr(C)]
+        struct Record {
+            header: u8,
+            payload: [String; 2],
+        }
+
+        let record = Box::new(Record {
+            header: 42,
+            payload: [String::from("Hello"), String::from("World")],
+        });
+        let erased = ErasedBox::new(record);
+
+        let (header, payload) = unsafe { erased.split_header::<u8, [String; 2]>() };
+        assert_eq!(*header, 42);
+        assert_eq!(payload.layout(), Layout::new::<[String; 2]>());
+        assert_eq!(
+            *unsafe { payload.into_inner::<[String; 2]>() },
+            ["Hello", "World"]
+        );
+    }
+
+    #[test]
+    fn descriptor_and_payload_test() {
+        #[repr(C)]
+        struct Record {
+            descriptor: [u32; 2],
+            payload: (u32, String),
+        }
+
+        let record = Box::new(Record {
+            descriptor: [1, 2],
+            payload: (3, String::from("Hello World")),
+        });
+        let erased = ErasedBox::new(record);
+
+        let (descriptor, payload) =
+            unsafe { erased.into_descriptor_and_payload::<[u32; 2], (u32, String)>() };
+        assert_eq!(descriptor, [1, 2]);
+        assert_eq!(payload.layout(), Layout::new::<(u32, String)>());
+        let payload = unsafe { payload.into_inner::<(u32, String)>() };
+        assert_eq!(payload.0, 3);
+        assert_eq!(payload.1, "Hello World");
+    }
+
+    #[test]
+    fn descriptor_only_test() {
+        let erased = ErasedBox::new(Box::new(5u64));
+        let (descriptor, payload) = unsafe { erased.into_descriptor_and_payload::<u64, ()>() };
+        assert_eq!(descriptor, 5);
+        assert_eq!(payload.layout().size(), 0);
+        unsafe { payload.drop_as::<()>() };
+    }
+
+    #[test]
+    fn owned_descriptor_and_payload_test() {
+        #[repr(C)]
+        struct Record {
+            descriptor: u8,
+            payload: DropCounter,
+        }
+
+        let drops = Rc::new(Cell::new(0));
+        let erased = ErasedBox::new_owned(Box::new(Record {
+            descriptor: 7,
+            payload: DropCounter(drops.clone()),
+        }));
+        let (descriptor, payload) =
+            unsafe { erased.into_descriptor_and_payload::<u8, DropCounter>() };
+        assert_eq!(descriptor, 7);
+        assert!(payload.needs_drop());
+        assert_eq!(drops.get(), 0);
+        drop(payload);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn finalizer_test() {
+        thread_local! {
+            static FINALIZED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        let erased = ErasedBox::with_finalizer(Box::new(String::from("Hello")), |s| {
+            FINALIZED.with_borrow_mut(|finalized| finalized.push(s.clone()))
+        });
+        assert!(FINALIZED.with_borrow(|finalized| finalized.is_empty()));
+
+        let v = unsafe { erased.into_inner::<String>() };
+        assert_eq!(*v, "Hello");
+        assert_eq!(
+            FINALIZED.with_borrow(|finalized| finalized.clone()),
+            ["Hello"]
+        );
+
+        let erased = ErasedBox::with_finalizer(Box::new(String::from("World")), |s| {
+            FINALIZED.with_borrow_mut(|finalized| finalized.push(s.clone()))
+        });
+        assert_eq!(unsafe { erased.get_ref::<String>() }, "World");
+        assert_eq!(unsafe { erased.size_as::<String>() }, size_of::<String>());
+        let dst = unsafe { alloc::alloc(erased.layout()) };
+        let erased = unsafe { erased.relocate::<String>(NonNull::new(dst).unwrap()) };
+        unsafe { erased.drop_as::<String>() };
+        assert_eq!(
+            FINALIZED.with_borrow(|finalized| finalized.clone()),
+            ["Hello", "World"]
+        );
+    }
+
+    /// A float that can also be viewed as its bits.
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    union Bits {
+        float: f64,
+        int: u64,
+    }
+
+    /// Counts how many times it is dropped.
+    #[derive(Clone)]
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn owned_drop_test() {
+        let drops = Rc::new(Cell::new(0));
+        let erased = ErasedBox::new_owned(Box::new(vec![
+            DropCounter(drops.clone()),
+            DropCounter(drops.clone()),
+        ]));
+        assert_eq!(drops.get(), 0);
+        drop(erased);
+        assert_eq!(drops.get(), 2);
+
+        let erased = ErasedBox::new_owned(Box::new(vec![String::from("Hello")]));
+        drop(erased);
+    }
+
+    #[test]
+    fn owned_into_inner_test() {
+        let drops = Rc::new(Cell::new(0));
+        let erased = ErasedBox::new_owned(Box::new(DropCounter(drops.clone())));
+        let v = unsafe { erased.into_inner::<DropCounter>() };
+        assert_eq!(drops.get(), 0);
+        drop(v);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn owned_transition_test() {
+        let drops = Rc::new(Cell::new(0));
+        let erased = ErasedBox::new_owned(Box::new(5u32));
+        let erased = unsafe { erased.transition::<u32, _>(|_| DropCounter(drops.clone())) };
+        drop(erased);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn owned_replace_with_panic_test() {
+        let drops = Rc::new(Cell::new(0));
+        let mut erased = ErasedBox::new_owned(Box::new(DropCounter(drops.clone())));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            erased.replace_with::<DropCounter>(|_| panic!("Replacement failed"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 1);
+        drop(erased);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn vtable_test() {
+        static VEC_VTABLE: ErasedVtable = ErasedVtable::of::<Vec<String>>();
+        static U32_VTABLE: ErasedVtable = ErasedVtable::of::<u32>();
+
+        let a = ErasedBox::with_vtable(Box::new(vec![String::from("Hello")]), &VEC_VTABLE);
+        let mut b = a.try_clone().unwrap();
+        assert_eq!(a.vtable_eq(&b), Some(true));
+        assert_eq!(format!("{:?}", b.vtable_debug().unwrap()), r#"["Hello"]"#);
+
+        let hash = |erased: &ErasedBox| {
+            let mut hasher = DefaultHasher::new();
+            assert!(erased.vtable_hash(&mut hasher));
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&b));
+
+        unsafe { b.get_mut::<Vec<String>>() }.push(String::from("World"));
+        assert_eq!(a.vtable_eq(&b), Some(false));
+        assert_eq!(unsafe { a.get_ref::<Vec<String>>() }, &["Hello"]);
+
+        let c = ErasedBox::with_vtable(Box::new(5u32), &U32_VTABLE);
+        assert_eq!(a.vtable_eq(&c), Some(false));
+        assert_eq!(format!("{:?}", c.vtable_debug().unwrap()), "5");
+
+        let d = ErasedBox::new_owned(Box::new(5u32));
+        assert!(d.try_clone().is_none());
+        assert_eq!(d.vtable_eq(&d), None);
+        assert_eq!(c.vtable_eq(&d), None);
+        assert!(d.vtable_debug().is_none());
+        assert!(!d.vtable_hash(&mut DefaultHasher::new()));
+        assert!(format!("{d:?}").starts_with("ErasedBox"));
+    }
+
+    #[test]
+    fn vtable_drop_test() {
+        #[derive(Clone, Debug, PartialEq, Hash)]
+        struct Counted(Rc<()>);
+
+        static VTABLE: ErasedVtable = ErasedVtable::of::<Counted>();
+
+        let rc = Rc::new(());
+        let a = ErasedBox::with_vtable(Box::new(Counted(rc.clone())), &VTABLE);
+        let b = a.try_clone().unwrap();
+        assert_eq!(Rc::strong_count(&rc), 3);
+        drop(a);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(b);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vtable was not created for `u64`")]
+    fn vtable_mismatch_test() {
+        static VTABLE: ErasedVtable = ErasedVtable::of::<u32>();
+        ErasedBox::with_vtable(Box::new(5u64), &VTABLE);
+    }
+
+    #[test]
+    fn emplace_test() {
+        let erased = ErasedBox::new(Box::new(String::from("Hello")));
+        let mut slot = MaybeUninit::<String>::uninit();
+        unsafe { erased.emplace::<String>(&mut slot) };
+        let mut value = unsafe { slot.assume_init() };
+        value.push_str(" World");
+        assert_eq!(value, "Hello World");
+
+        let erased = ErasedBox::new_aligned::<u16, 32>(5);
+        let mut slot = MaybeUninit::uninit();
+        unsafe { erased.emplace::<u16>(&mut slot) };
+        assert_eq!(unsafe { slot.assume_init() }, 5);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn rebox_in_test() {
+        use std::alloc::{AllocError, Allocator, System};
+
+        /// Counts the allocations made through it.
+        struct CountingAllocator<'a>(&'a Cell<usize>);
+
+        unsafe impl Allocator for CountingAllocator<'_> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.set(self.0.get() + 1);
+                System.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                System.deallocate(ptr, layout)
+            }
+        }
+
+        let allocations = Cell::new(0);
+        let erased = ErasedBox::new(Box::new(String::from("Hello World")));
+        let reboxed = unsafe { erased.rebox_in::<String, _>(CountingAllocator(&allocations)) };
+        assert_eq!(allocations.get(), 1);
+        assert_eq!(*reboxed, "Hello World");
+    }
+
+    #[test]
+    fn as_erased_test() {
+        struct Foo<'x>(&'x str);
+
+        let greeting = String::from("Hello World");
+        let boxed = ErasedBox::new(Box::new(Foo(&greeting)));
+        {
+            let erased = boxed.as_erased();
+            assert_eq!(unsafe { erased.get::<Foo<'_>>() }.0, "Hello World");
+            let foo: &Foo<'_> = unsafe { erased.get_with_lifetime::<'_, Foo<'_>>() };
+            assert_eq!(foo.0.len(), 11);
+        }
+        unsafe { boxed.drop_as::<Foo<'_>>() };
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn as_erased_checked_test() {
+        let boxed = ErasedBox::new_checked(Box::new(5u32));
+        assert!(boxed.as_erased().is::<u32>());
+        assert_eq!(*boxed.as_erased().get_checked::<u32>(), 5);
+        unsafe { boxed.drop_as::<u32>() };
+    }
+
+    #[test]
+    fn fold_as_test() {
+        let boxes: Vec<ErasedBox> = [4u32, 8, 15, 16, 23, 42]
+            .into_iter()
+            .map(|v| ErasedBox::new_owned(Box::new(v)))
+            .collect();
+        let total = unsafe { ErasedBox::fold_as::<u32, u32>(&boxes, 0, |acc, v| acc + v) };
+        assert_eq!(total, 108);
+
+        let strings = [
+            ErasedBox::new_owned(Box::new(String::from("Hello"))),
+            ErasedBox::new_owned(Box::new(String::from("World"))),
+        ];
+        let joined = unsafe {
+            ErasedBox::fold_as::<String, String>(&strings, String::new(), |acc, v| acc + v)
+        };
+        assert_eq!(joined, "HelloWorld");
+        assert_eq!(
+            unsafe { ErasedBox::fold_as::<u32, usize>(&[], 7, |acc, _| acc + 1) },
+            7
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "timed")]
+    fn into_inner_timed_test() {
+        use std::time::Duration;
+
+        let erased = ErasedBox::new(Box::new(5u32));
+        std::thread::sleep(Duration::from_millis(20));
+        let (value, elapsed) = unsafe { erased.into_inner_timed::<u32>() };
+        assert_eq!(*value, 5);
+        assert!(elapsed >= Duration::from_millis(20));
+        assert!(elapsed < Duration::from_secs(10));
+
+        let (_, elapsed) = unsafe { ErasedBox::new(Box::new(())).into_inner_timed::<()>() };
+        assert!(elapsed < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn into_inner_slice_test() {
+        let slice: Box<[u32]> = vec![1u32, 2, 3, 4, 5].into_boxed_slice();
+        let len = slice.len();
+        let mut erased = ErasedBox::new_slice(slice);
+        *unsafe { erased.get_mut::<u32>() } = 10;
+        let slice = unsafe { erased.into_inner_slice::<u32>(len) };
+        assert_eq!(*slice, [10, 2, 3, 4, 5]);
+
+        let strings = ErasedBox::new_slice(vec![String::from("Hello"); 3].into_boxed_slice());
+        assert_eq!(
+            unsafe { strings.into_inner_slice::<String>(3) }.concat(),
+            "HelloHelloHello"
+        );
+
+        let empty = ErasedBox::new_slice(Box::<[u64]>::default());
+        assert!(unsafe { empty.into_inner_slice::<u64>(0) }.is_empty());
+    }
+
+    #[test]
+    fn split_clone_test() {
+        let erased = ErasedBox::new(Box::new(vec![1u32, 2, 3]));
+        let (mut original, mut clone) = unsafe { erased.split_clone::<Vec<u32>>() };
+        original.push(4);
+        let cloned = unsafe { clone.get_mut::<Vec<u32>>() };
+        cloned[0] = 10;
+        assert_eq!(*original, [1, 2, 3, 4]);
+        assert_eq!(unsafe { clone.get_ref::<Vec<u32>>() }, &[10, 2, 3]);
+        unsafe { clone.drop_as::<Vec<u32>>() };
+
+        let drops = Rc::new(Cell::new(0));
+        let erased = ErasedBox::new_owned(Box::new(DropCounter(drops.clone())));
+        let (original, clone) = unsafe { erased.split_clone::<DropCounter>() };
+        drop(clone);
+        assert_eq!(drops.get(), 1);
+        drop(original);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn into_arc_any_test() {
+        let erased = ErasedBox::new_any_sync(Box::new(String::from("Hello World")));
+        let arc = erased.into_arc_any();
+        let clone = Arc::clone(&arc);
+        let len = std::thread::spawn(move || clone.downcast_ref::<String>().unwrap().len())
+            .join()
+            .unwrap();
+        assert_eq!(len, 11);
+        assert!(arc.downcast_ref::<u32>().is_none());
+        assert_eq!(arc.downcast::<String>().unwrap().as_str(), "Hello World");
+    }
+
+    #[test]
+    #[should_panic(expected = "Only erased boxes created using `new_any_sync`")]
+    fn into_any_vec_untracked_test() {
+        into_any_vec(vec![
+            ErasedBox::new_any_sync(Box::new(5u32)),
+            ErasedBox::new_owned(Box::new(5u32)),
+        ]);
+    }
+
+    #[test]
+    fn into_any_vec_test() {
+        let items = vec![
+            ErasedBox::new_any_sync(Box::new(5u32)),
+            ErasedBox::new_any_sync(Box::new(String::from("Hello World"))),
+            ErasedBox::new_any_sync(Box::new(vec![1.5f64])),
+        ];
+        let anys = into_any_vec(items);
+        assert_eq!(anys[0].downcast_ref::<u32>(), Some(&5));
+        assert_eq!(anys[1].downcast_ref::<String>().unwrap(), "Hello World");
+        assert!(anys[2].downcast_ref::<String>().is_none());
+        assert_eq!(anys[2].downcast_ref::<Vec<f64>>().unwrap(), &[1.5]);
+    }
+
+    #[test]
+    fn drop_all_test() {
+        let drops = Rc::new(Cell::new(0));
+        let boxes = vec![
+            ErasedBox::new_owned(Box::new(DropCounter(drops.clone()))),
+            ErasedBox::new_owned(Box::new((5u32, DropCounter(drops.clone())))),
+            ErasedBox::new_owned(Box::new(vec![
+                DropCounter(drops.clone()),
+                DropCounter(drops.clone()),
+            ])),
+            ErasedBox::new_owned(Box::new(String::from("Hello World"))),
+        ];
+        drop_all(boxes);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only erased boxes with drop glue can be dropped using `drop_all`")]
+    fn drop_all_without_glue_test() {
+        drop_all(vec![ErasedBox::new(Box::new(()))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only erased boxes created using `new_any_sync`")]
+    fn into_arc_any_untracked_test() {
+        ErasedBox::new_owned(Box::new(5u32)).into_arc_any();
+    }
+
+    #[test]
+    fn init_or_cleanup_test() {
+        let mut erased = ErasedBox::new_zeroed::<(u32, String)>();
+        let result = unsafe {
+            erased.init_or_cleanup::<(u32, String), ()>(|slot| {
+                slot.write((5, String::from("Hello World")));
+                Ok(())
+            })
+        };
+        assert_eq!(result, Ok(()));
+        let value = unsafe { erased.assume_init::<(u32, String)>() };
+        assert_eq!(*value, (5, String::from("Hello World")));
+    }
+
+    #[test]
+    fn init_or_cleanup_error_test() {
+        let drops = Rc::new(Cell::new(0));
+        let mut erased = ErasedBox::new_owned(Box::new(MaybeUninit::<DropCounter>::uninit()));
+        let result = unsafe {
+            erased.init_or_cleanup::<DropCounter, DropCounter>(|slot| {
+                slot.write(DropCounter(drops.clone()));
+                // Clean up the half-built value by handing it back in the error.
+                Err(slot.assume_init_read())
+            })
+        };
+        drop(erased);
+        assert_eq!(drops.get(), 0);
+        drop(result.unwrap_err());
+        assert_eq!(drops.get(), 1);
+
+        let mut erased = ErasedBox::new_zeroed::<DropCounter>();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            erased.init_or_cleanup::<DropCounter, ()>(|_| panic!("Initialization panicked"))
+        }));
+        assert!(result.is_err());
+        let result = unsafe {
+            erased.init_or_cleanup::<DropCounter, ()>(|slot| {
+                slot.write(DropCounter(drops.clone()));
+                Ok(())
+            })
+        };
+        assert_eq!(result, Ok(()));
+        drop(unsafe { erased.assume_init::<DropCounter>() });
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn send_as_test() {
+        let (tx, rx) = std::sync::mpsc::channel::<String>();
+        let boxes: Vec<ErasedBox> = ["Hello", "World"]
+            .into_iter()
+            .map(|s| ErasedBox::new(Box::new(String::from(s))))
+            .collect();
+        for erased in boxes {
+            unsafe { erased.send_as::<String>(&tx) }.unwrap();
+        }
+        drop(tx);
+        let received: Vec<String> = std::thread::spawn(move || rx.iter().collect())
+            .join()
+            .unwrap();
+        assert_eq!(received, ["Hello", "World"]);
+
+        let (tx, rx) = std::sync::mpsc::channel::<u32>();
+        drop(rx);
+        let result = unsafe { ErasedBox::new_owned(Box::new(5u32)).send_as::<u32>(&tx) };
+        assert_eq!(result.unwrap_err().0, 5);
+    }
+
+    #[test]
+    fn drop_as_test() {
+        let drops = Rc::new(Cell::new(0));
+        let erased = ErasedBox::new(Box::new(DropCounter(drops.clone())));
+        assert_eq!(drops.get(), 0);
+        unsafe { erased.drop_as::<DropCounter>() };
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "bumpalo")]
+    fn move_into_arena_test() {
+        let arena = bumpalo::Bump::new();
+        let erased = ErasedBox::new(Box::new(String::from("Hello")));
+        let s = unsafe { erased.move_into_arena::<String>(&arena) };
+        s.push_str(" World");
+        assert_eq!(s, "Hello World");
+        // The arena does not drop the string, so drop it in place to not leak it.
+        unsafe { std::ptr::drop_in_place(s) };
+    }
+
+    #[test]
+    fn size_as_test() {
+        let erased = ErasedBox::new(Box::new([0u16; 3]));
+        assert_eq!(unsafe { erased.size_as::<[u16; 3]>() }, 6);
+        unsafe { erased.drop_as::<[u16; 3]>() };
+
+        let erased = ErasedBox::new_aligned::<u8, 64>(5);
+        assert_eq!(unsafe { erased.size_as::<u8>() }, 1);
+        unsafe { erased.drop_as::<u8>() };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn size_as_mismatch_test() {
+        let erased = ErasedBox::new(Box::new(5u32));
+        let result = panic::catch_unwind(|| unsafe { erased.size_as::<u64>() });
+        assert!(result.is_err());
+        unsafe { erased.drop_as::<u32>() };
+    }
+
+    #[test]
+    fn read_union_test() {
+        let erased = ErasedBox::new(Box::new(1.5f64));
+        let bits = unsafe { erased.read_union::<Bits>() };
+        assert_eq!(unsafe { bits.int }, 1.5f64.to_bits());
+        assert_eq!(unsafe { bits.float }, 1.5);
+        unsafe { erased.drop_as::<f64>() };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn read_union_too_large_test() {
+        let erased = ErasedBox::new(Box::new(5u32));
+        let result = panic::catch_unwind(|| unsafe { erased.read_union::<Bits>() });
+        assert!(result.is_err());
+        unsafe { erased.drop_as::<u32>() };
+    }
+
+    #[test]
+    fn as_mut_c_void_test() {
+        extern "C" fn callback(user_data: *mut c_void) {
+            unsafe { *user_data.cast::<u64>() += 1 };
+        }
+
+        let mut erased = ErasedBox::new(Box::new(41u64));
+        callback(erased.as_mut_c_void());
+        assert_eq!(*unsafe { erased.into_inner::<u64>() }, 42);
+    }
+
+    #[test]
+    fn from_c_void_test() {
+        extern "C" fn call_with(
+            user_data: *mut c_void,
+            f: extern "C" fn(*mut c_void) -> usize,
+        ) -> usize {
+            f(user_data)
+        }
+        extern "C" fn callback(user_data: *mut c_void) -> usize {
+            let erased = unsafe { ErasedBox::from_c_void::<String>(user_data) };
+            unsafe { erased.into_inner::<String>() }.len()
+        }
+
+        let erased = ErasedBox::new(Box::new(String::from("Hello World")));
+        assert_eq!(call_with(erased.into_c_void(), callback), 11);
+    }
+
+    #[test]
+    fn needs_drop_test() {
+        let string = ErasedBox::new(Box::new(String::from("Hello World")));
+        assert!(string.needs_drop());
+        unsafe { string.into_inner::<String>() };
+
+        let int = ErasedBox::new(Box::new(5u64));
+        assert!(!int.needs_drop());
+        unsafe { int.into_inner::<u64>() };
+    }
+
+    #[test]
+    fn cell_test() {
+        let erased = ErasedBox::new(Box::new(Cell::new(5u32)));
+        let shared = &erased;
+        assert_eq!(unsafe { shared.cell_get::<u32>() }, 5);
+        unsafe { shared.cell_set::<u32>(42) };
+        assert_eq!(unsafe { shared.cell_get::<u32>() }, 42);
+        assert_eq!(unsafe { erased.into_inner::<Cell<u32>>() }.get(), 42);
+    }
+
+    #[test]
+    fn relocate_test() {
+        let erased = ErasedBox::new(Box::new(String::from("Hello World")));
+        let dst = NonNull::new(unsafe { alloc::alloc(Layout::new::<String>()) }).unwrap();
+
+        let relocated = unsafe { erased.relocate::<String>(dst) };
+        assert_eq!(relocated.ptr(), dst.cast());
+        assert_eq!(*unsafe { relocated.into_inner::<String>() }, "Hello World");
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn matches_type_id_test() {
+        let erased = ErasedBox::new_checked(Box::new(5usize));
+        assert!(!erased.matches_type_id(TypeId::of::<u32>()));
+        assert!(erased.matches_type_id(TypeId::of::<usize>()));
+        assert!(erased.is::<usize>());
+        unsafe { erased.into_inner::<usize>() };
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn into_inner_or_else_test() {
+        let erased = ErasedBox::new_checked(Box::new(5u64));
+        let v = erased.into_inner_or_else::<u64>(|| unreachable!("Type matches"));
+        assert_eq!(*v, 5);
+
+        let erased = ErasedBox::new_checked(Box::new(5u64));
+        assert_eq!(*erased.into_inner_or_else(|| Box::new(7u32)), 7);
+
+        let erased = ErasedBox::new(Box::new(5u64));
+        assert_eq!(*erased.into_inner_or_else(|| Box::new(7u64)), 7);
+
+        let erased = ErasedBox::new_aligned::<u8, 64>(5);
+        assert_eq!(*erased.into_inner_or_else(|| Box::new(7u8)), 7);
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn get_checked_test() {
+        let erased = ErasedBox::new_checked(Box::new(String::from("Hello")));
+        assert_eq!(erased.get_checked::<String>(), "Hello");
+        unsafe { erased.drop_as::<String>() };
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn get_checked_mismatch_test() {
+        let erased = ErasedBox::new_checked(Box::new(5u8));
+        let result = panic::catch_unwind(|| erased.get_checked::<i8>().to_string());
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert_eq!(
+            *message,
+            "Erased value of type `u8` was recovered as type `i8`"
+        );
+        unsafe { erased.drop_as::<u8>() };
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn type_tags_test() {
+        let items = vec![
+            ErasedBox::new_checked(Box::new(5u32)),
+            ErasedBox::new_checked(Box::new(String::from("Hello"))),
+            ErasedBox::new(Box::new(5u8)),
+            ErasedBox::new_checked(Box::new(5u32)),
+        ];
+        assert_eq!(
+            crate::type_tags(&items).collect::<Vec<_>>(),
+            [
+                TypeId::of::<u32>(),
+                TypeId::of::<String>(),
+                TypeId::of::<u32>()
+            ]
+        );
+
+        let mut items = items.into_iter();
+        unsafe {
+            items.next().unwrap().drop_as::<u32>();
+            items.next().unwrap().drop_as::<String>();
+            items.next().unwrap().drop_as::<u8>();
+            items.next().unwrap().drop_as::<u32>();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn fingerprint_test() {
+        let a = ErasedBox::new_checked(Box::new(5u32));
+        let b = ErasedBox::new_checked(Box::new(5u32));
+        let c = ErasedBox::new_checked(Box::new(5u64));
+        assert_ne!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        unsafe {
+            a.into_inner::<u32>();
+            b.into_inner::<u32>();
+            c.into_inner::<u64>();
+        }
+    }
+
+    #[test]
+    fn aligned_test() {
+        let erased = ErasedBox::new_aligned::<[u8; 3], 64>([1, 2, 3]);
+        assert!(erased.ptr().as_ptr().addr().is_multiple_of(64));
+        assert_eq!(erased.layout().align(), 64);
+        assert_eq!(*unsafe { erased.into_inner::<[u8; 3]>() }, [1, 2, 3]);
+
+        let erased = ErasedBox::new_aligned::<String, 32>(String::from("Hello"));
+        assert!(erased.ptr().as_ptr().addr().is_multiple_of(32));
+        let mut erased = erased;
+        unsafe { erased.replace_with::<String>(|s| s + " World") };
+        assert_eq!(*unsafe { erased.into_inner::<String>() }, "Hello World");
+
+        let erased = ErasedBox::new_aligned::<(), 16>(());
+        assert!(erased.ptr().as_ptr().addr().is_multiple_of(16));
+        unsafe { erased.into_inner::<()>() };
+    }
+
+    #[test]
+    fn aligned_into_inner_test() {
+        let erased = ErasedBox::new_aligned::<u64, 64>(5);
+        let value = unsafe { erased.into_inner::<u64>() };
+        assert_eq!(*value, 5);
+        assert!(NonNull::from(&*value).as_ptr().addr().is_multiple_of(8));
+    }
+
+    #[test]
+    fn layout_test() {
+        let erased = ErasedBox::new(Box::new(5u32));
+        assert_eq!(erased.layout(), Layout::new::<u32>());
+        unsafe { erased.into_inner::<u32>() };
     }
 }
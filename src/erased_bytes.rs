@@ -0,0 +1,52 @@
+/// A view of the bytes of an erased value, created using `Erased::bytes_view`.
+///
+/// Unlike `Erased`, this view only gives access to the bytes of the value, the typed value can not be recovered from it.
+/// This can be used to let code inspect an erased value without being able to interpret it.
+///
+/// Example:
+/// ```rust
+/// use erased::Erased;
+/// use std::alloc::Layout;
+///
+/// let erased = Erased::new(&[1u8, 2, 3]);
+/// // Safety: The layout matches the type of the erased value, which has no padding.
+/// let view = unsafe { erased.bytes_view(Layout::new::<[u8; 3]>()) };
+/// assert_eq!(view.as_bytes(), [1, 2, 3]);
+/// ```
+///
+/// Recovering the typed value is not possible:
+/// ```rust,compile_fail
+/// use erased::Erased;
+/// use std::alloc::Layout;
+///
+/// let erased = Erased::new(&[1u8, 2, 3]);
+/// // Safety: The layout matches the type of the erased value, which has no padding.
+/// let view = unsafe { erased.bytes_view(Layout::new::<[u8; 3]>()) };
+/// let typed = unsafe { view.get::<[u8; 3]>() };
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ErasedBytes<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ErasedBytes<'a> {
+    /// Create a new view of `bytes`
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Get the bytes of the erased value
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Get the number of bytes of the erased value
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Check whether the erased value has a size of zero
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
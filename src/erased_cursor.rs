@@ -0,0 +1,106 @@
+use crate::Erased;
+use std::marker::PhantomData;
+use std::slice;
+
+/// An iterator over a slice of erased references, which all refer to a value of type `T`.
+///
+/// The type `T` is specified once when the cursor is created, instead of for every element.
+///
+/// Example:
+/// ```rust
+/// use erased::{Erased, ErasedCursor};
+///
+/// let values = [1u32, 2, 3];
+/// let erased: Vec<Erased> = values.iter().map(Erased::new).collect();
+///
+/// // Safety: All erased references were created from a `u32`
+/// let cursor = unsafe { ErasedCursor::<u32>::new(&erased) };
+/// assert_eq!(cursor.sum::<u32>(), 6);
+/// ```
+#[derive(Debug)]
+pub struct ErasedCursor<'a, T> {
+    iter: slice::Iter<'a, Erased<'a>>,
+    phantom: PhantomData<fn() -> &'a T>,
+}
+
+impl<'a, T> ErasedCursor<'a, T> {
+    /// Create a new cursor over the erased references in `erased`, which are recovered as `T`.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create each of the erased references in `erased` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn new(erased: &'a [Erased<'a>]) -> ErasedCursor<'a, T> {
+        Self {
+            iter: erased.iter(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Create a new cursor over the erased references in `erased`, if they were all created from a `T` using `Erased::new_checked`.
+    /// Returns `None` if any of the values is not a `T`, or was not created using `Erased::new_checked`.
+    #[cfg(feature = "checked")]
+    pub fn new_checked(erased: &'a [Erased<'a>]) -> Option<ErasedCursor<'a, T>>
+    where
+        T: 'static,
+    {
+        // Safety: The type id of every value matches, so they are all a `T`.
+        erased
+            .iter()
+            .all(Erased::is::<T>)
+            .then(|| unsafe { Self::new(erased) })
+    }
+}
+
+impl<'a, T> Iterator for ErasedCursor<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        // Safety: From the safety comment of `ErasedCursor::new`, every erased reference was created from a `T`.
+        self.iter.next().map(|erased| unsafe { erased.get::<T>() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for ErasedCursor<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Erased, ErasedCursor};
+
+    #[test]
+    fn basic_test() {
+        let values = [String::from("Hello"), String::from("World")];
+        let erased: Vec<Erased> = values.iter().map(Erased::new).collect();
+
+        let mut cursor = unsafe { ErasedCursor::<String>::new(&erased) };
+        assert_eq!(cursor.len(), 2);
+        assert_eq!(cursor.next().map(String::as_str), Some("Hello"));
+        assert_eq!(cursor.next().map(String::as_str), Some("World"));
+        assert_eq!(cursor.next(), None);
+    }
+
+    #[test]
+    fn empty_test() {
+        let mut cursor = unsafe { ErasedCursor::<u64>::new(&[]) };
+        assert_eq!(cursor.next(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn checked_test() {
+        let values = [1u64, 2, 3];
+        let mut erased: Vec<Erased> = values.iter().map(Erased::new_checked).collect();
+
+        let cursor = ErasedCursor::<u64>::new_checked(&erased).unwrap();
+        assert_eq!(cursor.copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(ErasedCursor::<u32>::new_checked(&erased).is_none());
+
+        erased.push(Erased::new(&4u64));
+        assert!(ErasedCursor::<u64>::new_checked(&erased).is_none());
+    }
+}
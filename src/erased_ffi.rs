@@ -0,0 +1,22 @@
+/// An FFI-safe representation of an erased reference, created using `Erased::to_ffi`.
+///
+/// The lifetime of the erased reference is not represented, so converting it back using `Erased::from_ffi` is unsafe.
+///
+/// Example:
+/// ```rust
+/// use erased::{Erased, ErasedFfi};
+///
+/// let value = 5usize;
+/// let ffi: ErasedFfi = Erased::new(&value).to_ffi();
+///
+/// // Safety: `ffi` was created from a valid `Erased`, and `value` is still alive.
+/// let erased = unsafe { Erased::from_ffi(ffi) };
+/// // Safety: Matches the type of `value` exactly, which was used to create the `erased` value
+/// assert_eq!(unsafe { *erased.get::<usize>() }, 5);
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct ErasedFfi {
+    /// The address of the erased value
+    pub ptr: *const (),
+}
@@ -0,0 +1,185 @@
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+/// The inline buffer of an `ErasedInline`.
+type Buffer = [MaybeUninit<usize>; 2];
+
+/// An erased value `T`, which is stored inline if it is small enough, and in a box otherwise.
+///
+/// Values that fit in two `usize`s, with an alignment of at most that of `usize`, are stored without allocating.
+///
+/// # Warning
+/// This type **leaks** the value when it is dropped.
+/// To ensure that the value is not leaked, call `into_inner` on it before it is dropped.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedInline;
+///
+/// let erased = ErasedInline::new(5u64);
+/// assert!(erased.is_inline());
+///
+/// /// Safety: The type given to `into_inner` matches the type of the value.
+/// let v: u64 = unsafe { erased.into_inner::<u64>() };
+/// assert_eq!(v, 5);
+/// ```
+///
+/// Like the other erased types, an erased value can't be sent to another thread, since the type of the value may not be `Send`:
+/// ```rust,compile_fail
+/// use erased::ErasedInline;
+/// use std::rc::Rc;
+///
+/// let erased = ErasedInline::new(Rc::new(5u64));
+/// std::thread::spawn(move || unsafe { erased.into_inner::<Rc<u64>>() });
+/// ```
+#[derive(Debug)]
+pub struct ErasedInline {
+    // The value may have interior mutability, so it must be accessed through the `UnsafeCell`.
+    buffer: UnsafeCell<Buffer>,
+    inline: bool,
+    // The value may not be `Send` or `Sync`, so neither is the erased value.
+    phantom: PhantomData<*mut ()>,
+}
+
+impl ErasedInline {
+    /// Check whether a `T` is stored inline
+    const fn fits<T>() -> bool {
+        size_of::<T>() <= size_of::<Buffer>() && align_of::<T>() <= align_of::<Buffer>()
+    }
+
+    /// Create a new erased value from a `T`, which is stored inline if it fits, and in a box otherwise.
+    pub fn new<T>(t: T) -> ErasedInline {
+        let mut buffer = [MaybeUninit::uninit(); 2];
+        let inline = Self::fits::<T>();
+        if inline {
+            // Safety: `T` fits in the buffer, and its alignment is at most that of the buffer.
+            unsafe { buffer.as_mut_ptr().cast::<T>().write(t) };
+        } else {
+            let ptr = NonNull::from(Box::leak(Box::new(t))).cast::<()>();
+            // Safety: A pointer fits in the buffer, and its alignment is that of the buffer.
+            unsafe { buffer.as_mut_ptr().cast::<NonNull<()>>().write(ptr) };
+        }
+        Self {
+            buffer: UnsafeCell::new(buffer),
+            inline,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Check whether the value is stored inline, instead of in a box
+    pub fn is_inline(&self) -> bool {
+        self.inline
+    }
+
+    /// Get a pointer to the value, wherever it is stored.
+    fn ptr(&self) -> NonNull<()> {
+        let buffer = self.buffer.get();
+        if self.inline {
+            // Safety: The pointer to the contents of an `UnsafeCell` is never null.
+            unsafe { NonNull::new_unchecked(buffer).cast() }
+        } else {
+            // Safety: The value is not inline, so the buffer contains a pointer to the box.
+            unsafe { buffer.cast::<NonNull<()>>().read() }
+        }
+    }
+
+    /// Get the value `T` back from the erased value.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased value in `ErasedInline::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn into_inner<T>(self) -> T {
+        debug_assert_eq!(self.inline, Self::fits::<T>());
+        if self.inline {
+            // Safety: From the safety comment the `T` matches the `T` this erased value was created with, which is stored inline.
+            self.buffer.get().cast::<T>().read()
+        } else {
+            // Safety: From the safety comment the `T` matches the `T` this erased value was created with, which is stored in a box.
+            *Box::from_raw(self.ptr().cast::<T>().as_ptr())
+        }
+    }
+
+    /// Get a reference to the value.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased value in `ErasedInline::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get_ref<T>(&self) -> &T {
+        // Safety: From the safety comment the `T` matches the `T` this erased value was created with. The reference borrows self which owns the value, so its lifetime is valid.
+        self.ptr().cast::<T>().as_ref()
+    }
+
+    /// Get a mutable reference to the value.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased value in `ErasedInline::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get_mut<T>(&mut self) -> &mut T {
+        let mut ptr = if self.inline {
+            NonNull::from(self.buffer.get_mut()).cast::<T>()
+        } else {
+            self.ptr().cast::<T>()
+        };
+        // Safety: From the safety comment the `T` matches the `T` this erased value was created with. The reference borrows self which owns the value, so its lifetime is valid.
+        // Self is borrowed mutably ensuring exclusive access.
+        ptr.as_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedInline;
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn inline_test() {
+        let mut erased = ErasedInline::new(5u64);
+        assert!(erased.is_inline());
+        *unsafe { erased.get_mut::<u64>() } += 1;
+        assert_eq!(*unsafe { erased.get_ref::<u64>() }, 6);
+        assert_eq!(unsafe { erased.into_inner::<u64>() }, 6);
+
+        let erased = ErasedInline::new((1u32, 2usize));
+        assert!(erased.is_inline());
+        assert_eq!(unsafe { erased.into_inner::<(u32, usize)>() }, (1, 2));
+    }
+
+    #[test]
+    fn boxed_test() {
+        let mut erased = ErasedInline::new([7u64; 8]);
+        assert!(!erased.is_inline());
+
+        let array = unsafe { erased.get_mut::<[u64; 8]>() };
+        array[0] = 1;
+        assert_eq!(unsafe { erased.get_ref::<[u64; 8]>() }[..2], [1, 7]);
+        assert_eq!(unsafe { erased.into_inner::<[u64; 8]>() }[..2], [1, 7]);
+    }
+
+    #[test]
+    fn interior_mutability_test() {
+        let erased = ErasedInline::new(Cell::new(5u32));
+        assert!(erased.is_inline());
+        unsafe { erased.get_ref::<Cell<u32>>() }.set(6);
+        assert_eq!(unsafe { erased.into_inner::<Cell<u32>>() }.get(), 6);
+
+        let erased = ErasedInline::new(AtomicU64::new(5));
+        unsafe { erased.get_ref::<AtomicU64>() }.fetch_add(1, Ordering::Relaxed);
+        assert_eq!(unsafe { erased.into_inner::<AtomicU64>() }.into_inner(), 6);
+    }
+
+    #[test]
+    fn drop_type_test() {
+        let erased = ErasedInline::new(String::from("Hello World"));
+        assert_eq!(unsafe { erased.get_ref::<String>() }, "Hello World");
+        assert_eq!(unsafe { erased.into_inner::<String>() }, "Hello World");
+    }
+}
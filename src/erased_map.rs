@@ -0,0 +1,273 @@
+use crate::ErasedBox;
+use std::any::{type_name, TypeId};
+use std::collections::hash_map::{self, HashMap};
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
+
+/// A map from `String` keys to values of different types.
+///
+/// The type of each value is stored alongside it, so values can only be retrieved as the type they were inserted as.
+/// Unlike `ErasedBox`, the values in this map are dropped when they are removed or when the map is dropped.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedMap;
+///
+/// let mut map = ErasedMap::new();
+/// map.insert(String::from("answer"), 42u64);
+/// map.insert(String::from("greeting"), "Hello World");
+///
+/// assert_eq!(map.get::<u64>("answer"), Some(&42));
+/// assert_eq!(map.get::<&str>("greeting"), Some(&"Hello World"));
+/// assert_eq!(map.get::<u32>("answer"), None);
+///
+/// *map.entry::<u64>(String::from("counter")).or_insert(0) += 1;
+/// assert_eq!(map.get::<u64>("counter"), Some(&1));
+/// ```
+#[derive(Debug, Default)]
+pub struct ErasedMap {
+    map: HashMap<String, ErasedValue>,
+}
+
+/// A value in an `ErasedMap`, together with its type and a function to drop it.
+#[derive(Debug)]
+struct ErasedValue {
+    erased: ManuallyDrop<ErasedBox>,
+    type_id: TypeId,
+    drop: unsafe fn(ErasedBox),
+}
+
+impl ErasedValue {
+    fn new<T: 'static>(value: T) -> Self {
+        Self {
+            erased: ManuallyDrop::new(ErasedBox::new(Box::new(value))),
+            type_id: TypeId::of::<T>(),
+            // Safety: This function is only called with the erased box created above.
+            drop: |erased| unsafe { erased.drop_as::<T>() },
+        }
+    }
+
+    fn is<T: 'static>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+
+    /// Panics if the value is not a `T`.
+    fn assert_is<T: 'static>(&self, key: &str) {
+        assert!(
+            self.is::<T>(),
+            "Value for key `{key}` is not of type `{}`",
+            type_name::<T>()
+        );
+    }
+
+    fn get_ref<T: 'static>(&self) -> Option<&T> {
+        // Safety: The type id matches, so the value is a `T`.
+        self.is::<T>()
+            .then(|| unsafe { self.erased.get_ref::<T>() })
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        // Safety: The type id matches, so the value is a `T`.
+        self.is::<T>()
+            .then(|| unsafe { self.erased.get_mut::<T>() })
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn into_inner<T: 'static>(mut self) -> Result<Box<T>, Self> {
+        if !self.is::<T>() {
+            return Err(self);
+        }
+        // Safety: `self` is forgotten below, so the erased box is not dropped again.
+        let erased = unsafe { ManuallyDrop::take(&mut self.erased) };
+        mem::forget(self);
+        // Safety: The type id matches, so the value is a `T`.
+        Ok(unsafe { erased.into_inner::<T>() })
+    }
+}
+
+impl Drop for ErasedValue {
+    fn drop(&mut self) {
+        // Safety: The erased box is not used after this, and `drop` was created for the type of the value.
+        unsafe { (self.drop)(ManuallyDrop::take(&mut self.erased)) }
+    }
+}
+
+impl ErasedMap {
+    /// Create a new empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of values in the map
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check whether the map contains no values
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Check whether the map contains a value for `key`, of any type
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Insert a value for `key`, dropping the previous value for `key` if there was one.
+    pub fn insert<T: 'static>(&mut self, key: String, value: T) {
+        self.map.insert(key, ErasedValue::new(value));
+    }
+
+    /// Get a reference to the value for `key`.
+    /// Returns `None` if there is no value for `key`, or if it is not a `T`.
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.map.get(key)?.get_ref::<T>()
+    }
+
+    /// Get a mutable reference to the value for `key`.
+    /// Returns `None` if there is no value for `key`, or if it is not a `T`.
+    pub fn get_mut<T: 'static>(&mut self, key: &str) -> Option<&mut T> {
+        self.map.get_mut(key)?.get_mut::<T>()
+    }
+
+    /// Remove the value for `key` and return it.
+    /// Returns `None` if there is no value for `key`, or if it is not a `T`, in which case the value stays in the map.
+    pub fn remove<T: 'static>(&mut self, key: &str) -> Option<T> {
+        let value = self.map.remove_entry(key)?;
+        match value.1.into_inner::<T>() {
+            Ok(value) => Some(*value),
+            Err(other) => {
+                self.map.insert(value.0, other);
+                None
+            }
+        }
+    }
+
+    /// Get the entry for `key`, for in-place manipulation of a value of type `T`.
+    ///
+    /// # Panics
+    /// Panics if the map contains a value for `key` that is not a `T`.
+    pub fn entry<T: 'static>(&mut self, key: String) -> ErasedEntry<'_, T> {
+        let entry = self.map.entry(key);
+        if let hash_map::Entry::Occupied(occupied) = &entry {
+            occupied.get().assert_is::<T>(occupied.key());
+        }
+        ErasedEntry {
+            entry,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// An entry in an `ErasedMap` for a value of type `T`, which may be occupied or vacant.
+/// Created using `ErasedMap::entry`.
+#[derive(Debug)]
+pub struct ErasedEntry<'m, T> {
+    entry: hash_map::Entry<'m, String, ErasedValue>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'m, T: 'static> ErasedEntry<'m, T> {
+    /// Get the key of this entry
+    pub fn key(&self) -> &str {
+        self.entry.key()
+    }
+
+    /// Check whether the map contains a value for the key of this entry
+    pub fn is_occupied(&self) -> bool {
+        matches!(self.entry, hash_map::Entry::Occupied(_))
+    }
+
+    /// Insert `value` if the entry is vacant, and return a mutable reference to the value in the entry.
+    pub fn or_insert(self, value: T) -> &'m mut T {
+        self.or_insert_with(|| value)
+    }
+
+    /// Insert the result of `f` if the entry is vacant, and return a mutable reference to the value in the entry.
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'m mut T {
+        self.entry
+            .or_insert_with(|| ErasedValue::new(f()))
+            .get_mut::<T>()
+            .expect("Type of the entry was checked in `ErasedMap::entry`")
+    }
+
+    /// Insert the default value of `T` if the entry is vacant, and return a mutable reference to the value in the entry.
+    pub fn or_default(self) -> &'m mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedMap;
+    use std::rc::Rc;
+
+    #[test]
+    fn basic_test() {
+        let mut map = ErasedMap::new();
+        map.insert(String::from("a"), 5u64);
+        map.insert(String::from("b"), String::from("Hello"));
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get::<u64>("a"), Some(&5));
+        assert_eq!(map.get::<u32>("a"), None);
+        assert_eq!(map.get::<u64>("c"), None);
+
+        map.get_mut::<String>("b").unwrap().push_str(" World");
+        assert_eq!(map.remove::<u64>("b"), None);
+        assert_eq!(map.remove::<String>("b").as_deref(), Some("Hello World"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn drop_test() {
+        let value = Rc::new(5);
+        let mut map = ErasedMap::new();
+        map.insert(String::from("a"), value.clone());
+        map.insert(String::from("b"), value.clone());
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        map.insert(String::from("a"), 0u8);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(map);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn entry_vacant_test() {
+        let mut map = ErasedMap::new();
+        let entry = map.entry::<Vec<u32>>(String::from("a"));
+        assert!(!entry.is_occupied());
+        entry.or_insert_with(|| vec![1]).push(2);
+        assert_eq!(map.get::<Vec<u32>>("a"), Some(&vec![1, 2]));
+    }
+
+    #[test]
+    fn entry_occupied_test() {
+        let mut map = ErasedMap::new();
+        map.insert(String::from("a"), vec![1u32]);
+
+        let entry = map.entry::<Vec<u32>>(String::from("a"));
+        assert!(entry.is_occupied());
+        entry
+            .or_insert_with(|| unreachable!("Entry is occupied"))
+            .push(2);
+        assert_eq!(map.get::<Vec<u32>>("a"), Some(&vec![1, 2]));
+
+        *map.entry::<u32>(String::from("b")).or_default() += 3;
+        *map.entry::<u32>(String::from("b")).or_default() += 3;
+        assert_eq!(map.get::<u32>("b"), Some(&6));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not of type")]
+    fn entry_type_mismatch_test() {
+        let mut map = ErasedMap::new();
+        map.insert(String::from("a"), 5u64);
+        map.entry::<u32>(String::from("a"));
+    }
+}
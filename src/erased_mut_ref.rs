@@ -1,3 +1,9 @@
+#[cfg(feature = "checked")]
+use crate::checked::TypeInfo;
+use crate::{DetachedErasedMut, Erased};
+#[cfg(feature = "checked")]
+use std::any::TypeId;
+use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
@@ -17,10 +23,23 @@ use std::ptr::NonNull;
 /// // Safety: Matches the type of `value` exactly, which was used to create the `erased` value
 /// assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
 /// ```
+///
+/// # Aliasing
+/// Unlike `Erased`, this type does **not** implement `Clone` or `Copy`.
+/// A copy would allow two `&'a mut T` to be recovered from the same erased reference, which would alias.
+///
+/// ```rust,compile_fail
+/// use erased::ErasedMut;
+///
+/// let erased = ErasedMut::new(&mut 5usize);
+/// let copy: ErasedMut = erased.clone();
+/// ```
 #[derive(Debug)]
 pub struct ErasedMut<'a> {
     ptr: NonNull<()>,
     phantom: PhantomData<&'a ()>,
+    #[cfg(feature = "checked")]
+    type_info: Option<TypeInfo>,
 }
 
 impl<'a> ErasedMut<'a> {
@@ -29,9 +48,42 @@ impl<'a> ErasedMut<'a> {
         Self {
             ptr: NonNull::from(t).cast(),
             phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: None,
         }
     }
 
+    /// Create a new erased mutable reference from a mutable reference to `T`, recording the `TypeId` of `T`.
+    #[cfg(feature = "checked")]
+    pub fn new_checked<T: 'static>(t: &'a mut T) -> ErasedMut<'a> {
+        Self {
+            type_info: Some(TypeInfo::of::<T>()),
+            ..Self::new(t)
+        }
+    }
+
+    /// Check whether this erased mutable reference was created from a `T` using `ErasedMut::new_checked`.
+    #[cfg(feature = "checked")]
+    pub fn is<T: 'static>(&self) -> bool {
+        self.matches_type_id(TypeId::of::<T>())
+    }
+
+    /// Check whether this erased mutable reference was created using `ErasedMut::new_checked` from a type with the `TypeId` `id`.
+    /// This can be used when the expected type is only known at runtime.
+    #[cfg(feature = "checked")]
+    pub fn matches_type_id(&self, id: TypeId) -> bool {
+        self.type_info.is_some_and(|info| info.id == id)
+    }
+
+    /// Compute a fingerprint of this erased mutable reference, combining the `TypeId` recorded by `ErasedMut::new_checked` with the address of the value.
+    /// This can be used as a compact key for memoization tables, keyed on a specific value of a specific type.
+    ///
+    /// Note that addresses are reused after a value is freed, so a new value may have the same fingerprint as an old value that has since been freed.
+    #[cfg(feature = "checked")]
+    pub fn fingerprint(&self) -> u64 {
+        crate::checked::fingerprint(self.type_info, self.ptr)
+    }
+
     /// Get a mutable reference to `T` back from the erased mutable reference.
     ///
     /// # Safety
@@ -45,6 +97,17 @@ impl<'a> ErasedMut<'a> {
         self.ptr.cast::<T>().as_mut()
     }
 
+    /// Get a mutable reference to `T` back from an erased mutable reference that was created using `ErasedMut::new_checked`.
+    ///
+    /// # Panics
+    /// Panics if the erased reference was not created from a `T` using `ErasedMut::new_checked`, naming both the recorded and the requested type.
+    #[cfg(feature = "checked")]
+    pub fn get_checked<T: 'static>(&mut self) -> &'a mut T {
+        crate::checked::assert_type::<T>(self.type_info);
+        // Safety: The type id matches, so the value is a `T`.
+        unsafe { self.get::<T>() }
+    }
+
     /// Get a reference to `T` back from the erased mutable reference.
     ///
     /// # Safety
@@ -56,6 +119,88 @@ impl<'a> ErasedMut<'a> {
         // Safety: From the safety comment the `T` matches the `T` this reference was created with. The reference is still valid since its lifetime `'a` is still alive.
         self.ptr.cast::<T>().as_ref()
     }
+
+    /// Get the pointer to the erased value as a `*mut c_void`, for passing it to C APIs as a `void*` user-data pointer.
+    /// The pointer is valid for as long as `'a` is alive, and may be used for writes since this erased reference is borrowed mutably.
+    pub fn as_mut_c_void(&mut self) -> *mut c_void {
+        self.ptr.as_ptr().cast()
+    }
+
+    /// Recover an erased mutable reference from a `*mut c_void` user-data pointer, as passed back by a C callback.
+    ///
+    /// # Safety
+    /// `ptr` must be non-null, and must point to a value that stays alive and is not accessed through any other pointer for the lifetime `'a`, which is chosen by the caller.
+    /// Usually `ptr` was created using `ErasedMut::as_mut_c_void`, in which case `'a` must not outlive the mutable borrow of that erased reference.
+    pub unsafe fn from_c_void(ptr: *mut c_void) -> ErasedMut<'a> {
+        ErasedMut {
+            // Safety: From the safety comment the pointer is not null.
+            ptr: NonNull::new_unchecked(ptr.cast()),
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: None,
+        }
+    }
+
+    /// Reborrow this erased mutable reference with the shorter lifetime `'b`.
+    /// The original erased mutable reference can't be used while the reborrow is alive.
+    pub fn narrow<'b>(&'b mut self) -> ErasedMut<'b>
+    where
+        'a: 'b,
+    {
+        ErasedMut {
+            ptr: self.ptr,
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: self.type_info,
+        }
+    }
+
+    /// Reborrow this erased mutable reference with the lifetime of the borrow of `self`.
+    /// The original erased mutable reference can't be used while the reborrow is alive.
+    pub fn reborrow(&mut self) -> ErasedMut<'_> {
+        self.narrow()
+    }
+
+    /// Get a shared erased reference to the value, with the lifetime of the borrow of `self`.
+    /// The value can't be mutated through the original erased mutable reference while the shared erased reference is alive.
+    ///
+    /// Moving the erased mutable reference while the shared erased reference is alive fails to compile:
+    /// ```rust,compile_fail
+    /// use erased::ErasedMut;
+    ///
+    /// let mut value = 5usize;
+    /// let erased = ErasedMut::new(&mut value);
+    /// let shared = erased.as_shared();
+    /// let moved = erased;
+    /// assert_eq!(unsafe { *shared.get::<usize>() }, 5);
+    /// ```
+    pub fn as_shared(&self) -> Erased<'_> {
+        Erased {
+            ptr: self.ptr,
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: self.type_info,
+            #[cfg(feature = "checked")]
+            is_static: false,
+        }
+    }
+
+    /// Call `f` with a reborrowed erased mutable reference, which can't outlive the call.
+    /// This allows passing the erased mutable reference into callback-based APIs without consuming it.
+    pub fn with<R>(&mut self, f: impl FnOnce(ErasedMut<'_>) -> R) -> R {
+        f(ErasedMut {
+            ptr: self.ptr,
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: self.type_info,
+        })
+    }
+
+    /// Detach this erased mutable reference from its lifetime, so it can be stored in long-lived structures.
+    /// See `DetachedErasedMut` for the severe safety requirements of reattaching it.
+    pub fn detach(self) -> DetachedErasedMut {
+        DetachedErasedMut::new(self.ptr)
+    }
 }
 
 impl<'a, T> From<&'a mut T> for ErasedMut<'a> {
@@ -67,6 +212,9 @@ impl<'a, T> From<&'a mut T> for ErasedMut<'a> {
 #[cfg(test)]
 mod tests {
     use crate::ErasedMut;
+    #[cfg(feature = "checked")]
+    use std::any::TypeId;
+    use std::ffi::c_void;
 
     #[test]
     fn basic_test() {
@@ -77,4 +225,105 @@ mod tests {
         assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
         assert_eq!(*r1, 42);
     }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn get_checked_test() {
+        let value = &mut 5usize;
+        let mut erased = ErasedMut::new_checked(value);
+        *erased.get_checked::<usize>() = 42;
+        assert_eq!(*value, 42);
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "Erased value of type `usize` was recovered as type `isize`")]
+    fn get_checked_mismatch_test() {
+        ErasedMut::new_checked(&mut 5usize).get_checked::<isize>();
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn matches_type_id_test() {
+        let value = &mut 5usize;
+        let erased = ErasedMut::new_checked(value);
+        assert!(!erased.matches_type_id(TypeId::of::<u32>()));
+        assert!(erased.matches_type_id(TypeId::of::<usize>()));
+        assert!(erased.is::<usize>());
+    }
+
+    #[test]
+    fn narrow_test() {
+        struct Short<'b> {
+            erased: ErasedMut<'b>,
+        }
+
+        let r1 = &mut 5usize;
+        let mut long = ErasedMut::new(r1);
+        {
+            let mut short = Short {
+                erased: long.narrow(),
+            };
+            *unsafe { short.erased.get::<usize>() } = 42;
+        }
+        assert_eq!(*unsafe { long.get_ref::<usize>() }, 42);
+    }
+
+    #[test]
+    fn as_mut_c_void_test() {
+        extern "C" fn callback(user_data: *mut c_void) {
+            unsafe { *user_data.cast::<u64>() += 1 };
+        }
+
+        let r1 = &mut 41u64;
+        let mut erased = ErasedMut::new(r1);
+        callback(erased.as_mut_c_void());
+        assert_eq!(*unsafe { erased.get_ref::<u64>() }, 42);
+    }
+
+    #[test]
+    fn from_c_void_test() {
+        extern "C" fn call_with(user_data: *mut c_void, f: extern "C" fn(*mut c_void)) {
+            f(user_data)
+        }
+        extern "C" fn callback(user_data: *mut c_void) {
+            let mut erased = unsafe { ErasedMut::from_c_void(user_data) };
+            *unsafe { erased.get::<u64>() } += 1;
+        }
+
+        let r1 = &mut 41u64;
+        let mut erased = ErasedMut::new(r1);
+        call_with(erased.as_mut_c_void(), callback);
+        assert_eq!(*unsafe { erased.get_ref::<u64>() }, 42);
+    }
+
+    #[test]
+    fn with_test() {
+        let r1 = &mut 5usize;
+        let mut erased = ErasedMut::new(r1);
+        let old = erased.with(|mut reborrowed| {
+            let r2 = unsafe { reborrowed.get::<usize>() };
+            std::mem::replace(r2, 42)
+        });
+        assert_eq!(old, 5);
+        assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
+    }
+
+    #[test]
+    fn reborrow_test() {
+        fn push(mut erased: ErasedMut<'_>) {
+            unsafe { erased.get::<Vec<u32>>() }.push(4);
+        }
+
+        let mut value = vec![1u32, 2, 3];
+        let mut erased = ErasedMut::new(&mut value);
+        push(erased.reborrow());
+        push(erased.reborrow());
+        assert_eq!(
+            unsafe { erased.as_shared().get::<Vec<u32>>() },
+            &[1, 2, 3, 4, 4]
+        );
+        unsafe { erased.get::<Vec<u32>>() }.clear();
+        assert!(value.is_empty());
+    }
 }
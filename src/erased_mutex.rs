@@ -0,0 +1,194 @@
+use crate::ErasedBox;
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// An erased box `Box<T>` protected by a lock, for sharing erased state between threads.
+///
+/// Unlike a `Mutex<T>`, the type of the protected value is erased, so it must be provided when locking.
+/// The lock can also be acquired with a timeout using `ErasedMutex::try_lock_for`, to avoid deadlocks.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedMutex;
+/// use std::time::Duration;
+///
+/// let mutex = ErasedMutex::new(Box::new(5u32));
+///
+/// // Safety: Matches the type of the value the mutex was created with exactly
+/// let mut guard = unsafe { mutex.lock::<u32>() };
+/// *guard += 1;
+/// assert!(unsafe { mutex.try_lock_for::<u32>(Duration::from_millis(10)) }.is_none());
+/// drop(guard);
+///
+/// assert_eq!(*unsafe { mutex.lock::<u32>() }, 6);
+/// ```
+///
+/// Values that borrow from the stack can't be stored in the mutex, since they could outlive the borrow:
+/// ```rust,compile_fail
+/// use erased::ErasedMutex;
+///
+/// let value = String::from("Hello World");
+/// let mutex = ErasedMutex::new(Box::new(&value));
+/// drop(value);
+/// drop(mutex);
+/// ```
+#[derive(Debug)]
+pub struct ErasedMutex {
+    value: UnsafeCell<ErasedBox>,
+    locked: Mutex<bool>,
+    unlocked: Condvar,
+}
+
+// Safety: The value can only be accessed while holding the lock, and it was created from a `T: Send + 'static`.
+unsafe impl Send for ErasedMutex {}
+// Safety: The value can only be accessed while holding the lock, and it was created from a `T: Send + 'static`.
+unsafe impl Sync for ErasedMutex {}
+
+impl ErasedMutex {
+    /// Create a new unlocked mutex from a `Box<T>`.
+    ///
+    /// `T` must be `'static`, since the value may be dropped and accessed on any thread, long after any borrow it holds has ended.
+    pub fn new<T: Send + 'static>(t: Box<T>) -> ErasedMutex {
+        Self {
+            value: UnsafeCell::new(ErasedBox::new_owned(t)),
+            locked: Mutex::new(false),
+            unlocked: Condvar::new(),
+        }
+    }
+
+    /// Get the flag that tracks whether the mutex is locked.
+    /// The flag is only held briefly and never while user code runs, so poisoning can be ignored.
+    fn locked(&self) -> MutexGuard<'_, bool> {
+        self.locked.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Acquire the lock, blocking the current thread until it is available.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased mutex exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn lock<T>(&self) -> ErasedMutexGuard<'_, T> {
+        let locked = self.locked();
+        let mut locked = self
+            .unlocked
+            .wait_while(locked, |locked| *locked)
+            .unwrap_or_else(PoisonError::into_inner);
+        *locked = true;
+        ErasedMutexGuard {
+            mutex: self,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Try to acquire the lock, blocking the current thread for at most `dur` until it is available.
+    /// Returns `None` if the lock could not be acquired within `dur`.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased mutex exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn try_lock_for<T>(&self, dur: Duration) -> Option<ErasedMutexGuard<'_, T>> {
+        let locked = self.locked();
+        let (mut locked, _) = self
+            .unlocked
+            .wait_timeout_while(locked, dur, |locked| *locked)
+            .unwrap_or_else(PoisonError::into_inner);
+        if *locked {
+            return None;
+        }
+        *locked = true;
+        Some(ErasedMutexGuard {
+            mutex: self,
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// A guard for the value of an `ErasedMutex`, created using `ErasedMutex::lock` or `ErasedMutex::try_lock_for`.
+/// The lock is released when the guard is dropped.
+#[derive(Debug)]
+pub struct ErasedMutexGuard<'a, T> {
+    mutex: &'a ErasedMutex,
+    phantom: PhantomData<&'a mut T>,
+}
+
+impl<T> Deref for ErasedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: The guard holds the lock, and from the safety comment of `lock` the value is a `T`.
+        unsafe { (*self.mutex.value.get()).get_ref::<T>() }
+    }
+}
+
+impl<T> DerefMut for ErasedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: The guard holds the lock, and from the safety comment of `lock` the value is a `T`.
+        unsafe { (*self.mutex.value.get()).get_mut::<T>() }
+    }
+}
+
+impl<T> Drop for ErasedMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        *self.mutex.locked() = false;
+        self.mutex.unlocked.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedMutex;
+    use std::sync::{mpsc, Arc};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn lock_test() {
+        let mutex = Arc::new(ErasedMutex::new(Box::new(vec![1u32])));
+        let handles: Vec<_> = (2..6)
+            .map(|i| {
+                let mutex = mutex.clone();
+                thread::spawn(move || unsafe { mutex.lock::<Vec<u32>>() }.push(i))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut values = unsafe { mutex.lock::<Vec<u32>>() }.clone();
+        values.sort();
+        assert_eq!(values, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn try_lock_for_test() {
+        let mutex = Arc::new(ErasedMutex::new(Box::new(String::from("Hello"))));
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel();
+
+        let holder = {
+            let mutex = mutex.clone();
+            thread::spawn(move || {
+                let mut guard = unsafe { mutex.lock::<String>() };
+                locked_tx.send(()).unwrap();
+                release_rx.recv().unwrap();
+                guard.push_str(" World");
+            })
+        };
+
+        locked_rx.recv().unwrap();
+        assert!(unsafe { mutex.try_lock_for::<String>(Duration::from_millis(10)) }.is_none());
+
+        release_tx.send(()).unwrap();
+        let guard = unsafe { mutex.try_lock_for::<String>(Duration::from_secs(60)) }.unwrap();
+        assert_eq!(*guard, "Hello World");
+        drop(guard);
+        holder.join().unwrap();
+    }
+}
@@ -0,0 +1,153 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+/// An erased pinned mutable reference to a value `Pin<&'a mut T>`
+///
+/// Since the value is pinned, it is only ever given out as a `Pin<&mut T>`, so it can't be moved out of the erased reference.
+/// Use the `pin_erase_mut!` macro to pin a value on the stack and erase it at once.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedPinMut;
+/// use std::pin::pin;
+///
+/// let value = pin!(5usize);
+/// let mut erased = ErasedPinMut::new(value);
+///
+/// // Safety: Matches the type of `value` exactly, which was used to create the `erased` value
+/// let r2 = unsafe { erased.get::<usize>() };
+/// *r2.get_mut() = 42;
+///
+/// // Safety: Matches the type of `value` exactly, which was used to create the `erased` value
+/// assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
+/// ```
+#[derive(Debug)]
+pub struct ErasedPinMut<'a> {
+    ptr: NonNull<()>,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> ErasedPinMut<'a> {
+    /// Create a new erased pinned mutable reference from a pinned mutable reference to `T`
+    pub fn new<T>(t: Pin<&'a mut T>) -> ErasedPinMut<'a> {
+        Self {
+            // Safety: The value is never moved, since it is only given out again as a `Pin<&mut T>`.
+            ptr: NonNull::from(unsafe { t.get_unchecked_mut() }).cast(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get a pinned mutable reference to `T` back from the erased pinned mutable reference.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `ErasedPinMut::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get<T>(&mut self) -> Pin<&'a mut T> {
+        // Safety: From the safety comment the `T` matches the `T` this erased reference was created with.
+        // The mutable reference is still valid since its lifetime `'a` is still alive, and the `&mut self` provides exclusive access.
+        // The value was pinned when this erased reference was created, so it can be pinned again.
+        Pin::new_unchecked(self.ptr.cast::<T>().as_mut())
+    }
+
+    /// Get a pinned reference to `T` back from the erased pinned mutable reference.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `ErasedPinMut::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get_ref<T>(&self) -> Pin<&'a T> {
+        // Safety: From the safety comment the `T` matches the `T` this reference was created with. The reference is still valid since its lifetime `'a` is still alive.
+        // The value was pinned when this erased reference was created, so it can be pinned again.
+        Pin::new_unchecked(self.ptr.cast::<T>().as_ref())
+    }
+}
+
+impl<'a, T> From<Pin<&'a mut T>> for ErasedPinMut<'a> {
+    fn from(value: Pin<&'a mut T>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Pin the value in the local variable `$value` on the stack, and replace it with an `ErasedPinMut` to it.
+///
+/// This is similar to `std::pin::pin!`, combined with `ErasedPinMut::new`.
+/// The original value is shadowed, so it can no longer be moved.
+///
+/// Example:
+/// ```rust
+/// use erased::pin_erase_mut;
+///
+/// let value = 5usize;
+/// pin_erase_mut!(value);
+///
+/// // Safety: Matches the type of the original `value` exactly, which was used to create the erased value
+/// assert_eq!(*unsafe { value.get_ref::<usize>() }, 5);
+/// ```
+#[macro_export]
+macro_rules! pin_erase_mut {
+    ($value:ident) => {
+        let mut $value = $value;
+        #[allow(unused_mut)]
+        let mut $value = $crate::ErasedPinMut::new(
+            // Safety: The original value is shadowed, so it can't be moved anymore after it is pinned.
+            unsafe { ::core::pin::Pin::new_unchecked(&mut $value) },
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedPinMut;
+    use std::future::Future;
+    use std::marker::PhantomPinned;
+    use std::pin::{pin, Pin};
+    use std::task::{Context, Poll, Waker};
+
+    #[test]
+    fn basic_test() {
+        let value = pin!(5usize);
+        let mut erased = ErasedPinMut::new(value);
+        *unsafe { erased.get::<usize>() }.get_mut() = 42;
+        assert_eq!(*unsafe { erased.get_ref::<usize>() }, 42);
+    }
+
+    #[test]
+    fn macro_poll_test() {
+        /// A future that is ready after being polled `remaining` times, and is not `Unpin`.
+        struct Countdown {
+            remaining: u32,
+            _pinned: PhantomPinned,
+        }
+
+        impl Future for Countdown {
+            type Output = &'static str;
+
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+                // Safety: `remaining` is not structurally pinned.
+                let remaining = &mut unsafe { self.get_unchecked_mut() }.remaining;
+                if *remaining == 0 {
+                    Poll::Ready("done")
+                } else {
+                    *remaining -= 1;
+                    Poll::Pending
+                }
+            }
+        }
+
+        let future = Countdown {
+            remaining: 2,
+            _pinned: PhantomPinned,
+        };
+        pin_erase_mut!(future);
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut poll = || unsafe { future.get::<Countdown>() }.poll(&mut cx);
+        assert_eq!(poll(), Poll::Pending);
+        assert_eq!(poll(), Poll::Pending);
+        assert_eq!(poll(), Poll::Ready("done"));
+    }
+}
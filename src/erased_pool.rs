@@ -0,0 +1,139 @@
+use crate::ErasedBox;
+use std::alloc::{self, Layout};
+use std::collections::HashMap;
+use std::ptr::NonNull;
+
+/// A pool of allocations for erased boxes, grouped by layout.
+///
+/// Boxes that are returned to the pool using `ErasedPool::free` keep their allocation in the pool,
+/// so a later `ErasedPool::alloc` of a value with the same layout reuses it instead of calling the allocator.
+/// The allocations in the pool are freed when the pool is dropped.
+///
+/// The boxes created by the pool are normal erased boxes, so they can also be recovered using `ErasedBox::into_inner`, in which case the allocation is not returned to the pool.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedPool;
+///
+/// let mut pool = ErasedPool::new();
+/// let erased = pool.alloc(5u64);
+/// let address = erased.as_erased().as_c_void();
+/// // Safety: The box was created from a `u64`.
+/// unsafe { pool.free::<u64>(erased) };
+///
+/// let erased = pool.alloc(42u64);
+/// assert_eq!(erased.as_erased().as_c_void(), address);
+/// // Safety: The box was created from a `u64`.
+/// unsafe { pool.free::<u64>(erased) };
+/// ```
+#[derive(Debug, Default)]
+pub struct ErasedPool {
+    free: HashMap<Layout, Vec<NonNull<()>>>,
+}
+
+impl ErasedPool {
+    /// Create an empty pool
+    pub fn new() -> ErasedPool {
+        Self::default()
+    }
+
+    /// Create an erased box for `value`, reusing a free allocation with the layout of `T` if the pool has one.
+    pub fn alloc<T>(&mut self, value: T) -> ErasedBox {
+        let reused = self.free.get_mut(&Layout::new::<T>()).and_then(Vec::pop);
+        match reused {
+            Some(ptr) => {
+                let ptr = ptr.cast::<T>();
+                // Safety: The allocation was created by a `Box` with the layout of `T`, and the value in it was dropped in `free`.
+                unsafe {
+                    ptr.as_ptr().write(value);
+                    ErasedBox::new(Box::from_raw(ptr.as_ptr()))
+                }
+            }
+            None => ErasedBox::new(Box::new(value)),
+        }
+    }
+
+    /// Drop the value of type `T` in `erased`, and return its allocation to the pool.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create `erased` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn free<T>(&mut self, erased: ErasedBox) {
+        // Safety: From the safety comment the `T` matches the `T` `erased` was created with.
+        let ptr = Box::into_raw(erased.into_inner::<T>());
+        // Safety: The value is valid and owned by us, and is not used after it is dropped.
+        ptr.drop_in_place();
+        let layout = Layout::new::<T>();
+        if layout.size() != 0 {
+            self.free
+                .entry(layout)
+                .or_default()
+                .push(NonNull::new_unchecked(ptr).cast());
+        }
+    }
+
+    /// Get the number of free allocations in the pool
+    pub fn len(&self) -> usize {
+        self.free.values().map(Vec::len).sum()
+    }
+
+    /// Check whether the pool has no free allocations
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for ErasedPool {
+    fn drop(&mut self) {
+        for (layout, ptrs) in &self.free {
+            for ptr in ptrs {
+                // Safety: The allocation was created by a `Box` with `layout`, which is not zero-sized, and its value was dropped in `free`.
+                unsafe { alloc::dealloc(ptr.as_ptr().cast(), *layout) };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedPool;
+    use std::collections::HashSet;
+
+    #[test]
+    fn reuse_test() {
+        let mut pool = ErasedPool::new();
+        let mut addresses = HashSet::new();
+        for i in 0..100u64 {
+            let boxes = [pool.alloc(i), pool.alloc(i + 1)];
+            addresses.extend(boxes.iter().map(|erased| erased.as_erased().as_c_void()));
+            assert_eq!(*unsafe { boxes[1].get_ref::<u64>() }, i + 1);
+            for erased in boxes {
+                unsafe { pool.free::<u64>(erased) };
+            }
+        }
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn layouts_test() {
+        let mut pool = ErasedPool::new();
+        let string = pool.alloc(String::from("Hello World"));
+        let number = pool.alloc(5u32);
+        unsafe { pool.free::<String>(string) };
+        unsafe { pool.free::<u32>(number) };
+        assert_eq!(pool.len(), 2);
+
+        let float = pool.alloc(1.5f32);
+        assert_eq!(pool.len(), 1);
+        let unit = pool.alloc(());
+        unsafe { pool.free::<()>(unit) };
+        assert_eq!(pool.len(), 1);
+
+        let recovered = unsafe { float.into_inner::<f32>() };
+        assert_eq!(*recovered, 1.5);
+        assert!(!pool.is_empty());
+    }
+}
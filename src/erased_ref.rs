@@ -1,5 +1,20 @@
+#[cfg(feature = "checked")]
+use crate::checked::TypeInfo;
+#[cfg(feature = "prim_int")]
+use crate::PrimInt;
+#[cfg(feature = "checked")]
+use crate::{DebugNode, ReflectorRegistry};
+use crate::{ErasedBox, ErasedBytes, ErasedFfi, ErasedUnsized, LazyErased, SafeToErase};
+use std::alloc::Layout;
+#[cfg(feature = "checked")]
+use std::any::TypeId;
+use std::cell::OnceCell;
+use std::ffi::c_void;
+use std::io::{Cursor, Read};
+use std::iter;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
+use std::sync::MutexGuard;
 
 /// An erased reference to a value `&'a T`
 ///
@@ -18,8 +33,12 @@ use std::ptr::NonNull;
 /// ```
 #[derive(Copy, Clone, Debug)]
 pub struct Erased<'a> {
-    ptr: NonNull<()>,
-    phantom: PhantomData<&'a ()>,
+    pub(crate) ptr: NonNull<()>,
+    pub(crate) phantom: PhantomData<&'a ()>,
+    #[cfg(feature = "checked")]
+    pub(crate) type_info: Option<TypeInfo>,
+    #[cfg(feature = "checked")]
+    pub(crate) is_static: bool,
 }
 
 impl<'a> Erased<'a> {
@@ -28,11 +47,279 @@ impl<'a> Erased<'a> {
         Self {
             ptr: NonNull::from(t).cast(),
             phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: None,
+            #[cfg(feature = "checked")]
+            is_static: false,
         }
     }
 
+    /// Create a new erased reference from a reference to `T`, which must be vetted to be erased by implementing `SafeToErase`
+    pub fn new_safe<T: SafeToErase>(t: &'a T) -> Erased<'a> {
+        Self::new(t)
+    }
+
+    /// Create a new erased reference from a `'static` reference to `T`.
+    ///
+    /// With the `checked` feature, the erased reference remembers that the value lives for `'static`,
+    /// so its lifetime can later be extended back to `'static` using the safe `Erased::promote_to_static`.
+    /// Without it, the erased reference is the same as one created using `Erased::new`, since the flag would make every erased reference larger.
+    pub fn new_static<T>(t: &'static T) -> Erased<'a> {
+        Self {
+            #[cfg(feature = "checked")]
+            is_static: true,
+            ..Self::new(t)
+        }
+    }
+
+    /// Create a new erased reference to the value protected by a locked mutex.
+    ///
+    /// The erased reference borrows the guard, so it can only be used while the mutex is locked:
+    /// ```rust,compile_fail
+    /// use erased::Erased;
+    /// use std::sync::Mutex;
+    ///
+    /// let mutex = Mutex::new(5u32);
+    /// let guard = mutex.lock().unwrap();
+    /// let erased = Erased::from_mutex_guard(&guard);
+    /// drop(guard);
+    /// // Safety: Matches the type of the value in the mutex
+    /// assert_eq!(*unsafe { erased.get::<u32>() }, 5);
+    /// ```
+    pub fn from_mutex_guard<T>(guard: &'a MutexGuard<'_, T>) -> Erased<'a> {
+        Self::new(&**guard)
+    }
+
+    /// Create a new erased reference from a reference to `T`, and push its address and the name of `T` to the access log of the current thread.
+    /// The access log can be inspected using `dump_access_log`.
+    #[cfg(feature = "access_log")]
+    pub fn new_logged<T>(t: &'a T) -> Erased<'a> {
+        let erased = Self::new(t);
+        crate::access_log::log::<T>(erased.ptr);
+        erased
+    }
+
+    /// Create a new erased reference from a reference to `T`, recording the `TypeId` of `T`.
+    #[cfg(feature = "checked")]
+    pub fn new_checked<T: 'static>(t: &'a T) -> Erased<'a> {
+        Self {
+            type_info: Some(TypeInfo::of::<T>()),
+            ..Self::new(t)
+        }
+    }
+
+    /// Create a new erased reference from a mutable reference to `T`.
+    ///
+    /// The erased reference holds on to the exclusive borrow for `'a`, so the value can't be mutated through the original mutable reference while the erased reference is alive:
+    /// ```rust,compile_fail
+    /// use erased::Erased;
+    ///
+    /// let mut value = 5usize;
+    /// let exclusive = &mut value;
+    /// let erased = Erased::from_exclusive(exclusive);
+    /// *exclusive = 42;
+    /// // Safety: Matches the type used to create `erased`
+    /// assert_eq!(unsafe { *erased.get::<usize>() }, 5);
+    /// ```
+    pub fn from_exclusive<T>(t: &'a mut T) -> Erased<'a> {
+        Self::new(t)
+    }
+
+    /// Create a new erased reference to the value in `cell`, if it is initialized.
+    pub fn from_once<T>(cell: &'a OnceCell<T>) -> Option<Erased<'a>> {
+        cell.get().map(Self::new)
+    }
+
+    /// Create a new erased reference to the element at index `i` of `slice`.
+    /// Returns `None` if `i` is out of bounds.
+    pub fn from_slice_index<T>(slice: &'a [T], i: usize) -> Option<Erased<'a>> {
+        slice.get(i).map(Self::new)
+    }
+
+    /// Create a new erased reference from a reference to a trait object `Dyn`, such as `&dyn Display`, retaining its vtable.
+    ///
+    /// An `Erased` is one pointer wide, so it can't hold the vtable. This returns an `ErasedUnsized` instead, from which the trait object can be recovered using `ErasedUnsized::get_dyn`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use erased::{Erased, ErasedUnsized};
+    /// use std::fmt::Display;
+    ///
+    /// let vec: Vec<ErasedUnsized> = vec![Erased::new_dyn::<dyn Display>(&5u64), Erased::new_dyn::<dyn Display>(&"Hello World")];
+    ///
+    /// // SAFETY: Above we only inserted `dyn Display` references into the vec.
+    /// assert_eq!(unsafe { vec[1].get_dyn::<dyn Display>() }.to_string(), "Hello World");
+    /// ```
+    pub fn new_dyn<Dyn: ?Sized>(t: &'a Dyn) -> ErasedUnsized<'a> {
+        ErasedUnsized::new(t)
+    }
+
+    /// Compute a fingerprint of this erased reference, combining the `TypeId` recorded by `Erased::new_checked` with the address of the value.
+    /// This can be used as a compact key for memoization tables, keyed on a specific value of a specific type.
+    ///
+    /// Note that addresses are reused after a value is freed, so a new value may have the same fingerprint as an old value that has since been freed.
+    #[cfg(feature = "checked")]
+    pub fn fingerprint(&self) -> u64 {
+        crate::checked::fingerprint(self.type_info, self.ptr)
+    }
+
+    /// Build a tree of the fields of the value behind this erased reference, using the reflectors in `reflectors`.
+    /// Fields for which no reflector is registered are included as `DebugNode::Opaque`.
+    ///
+    /// Returns `None` if this erased reference was not created using `Erased::new_checked`, or if no reflector is registered for its type.
+    #[cfg(feature = "checked")]
+    pub fn debug_tree(&self, reflectors: &ReflectorRegistry) -> Option<DebugNode> {
+        reflectors.debug_tree(*self)
+    }
+
+    /// Shorten the lifetime of this erased reference to `'b`.
+    ///
+    /// Since `Erased` is covariant in `'a` this usually happens implicitly, but this method makes it explicit, which helps when the coercion does not fire, such as inside other generic types.
+    pub fn narrow<'b>(self) -> Erased<'b>
+    where
+        'a: 'b,
+    {
+        self
+    }
+
+    /// Extend the lifetime of this erased reference to `'static`.
+    ///
+    /// # Safety
+    /// The value behind this erased reference must live for the rest of the program, and must never be mutated or moved.
+    /// This is usually only true if the erased reference was created from a `&'static T`, in which case `Erased::promote_to_static` can be used instead with the `checked` feature.
+    pub unsafe fn assume_static(self) -> Erased<'static> {
+        Erased {
+            phantom: PhantomData,
+            ..self
+        }
+    }
+
+    /// Extend the lifetime of this erased reference to `'static`, if it was created from a `'static` reference using `Erased::new_static`.
+    /// Returns `None` otherwise.
+    ///
+    /// Erased references to fields of a value created using `Erased::new_static` can be promoted as well.
+    ///
+    /// This is only available with the `checked` feature, which records whether an erased reference is `'static`.
+    #[cfg(feature = "checked")]
+    pub fn promote_to_static(self) -> Option<Erased<'static>> {
+        // Safety: The erased reference was created from a `&'static T`, so the value lives for the rest of the program.
+        self.is_static.then(|| unsafe { self.assume_static() })
+    }
+
+    /// Reborrow this erased reference with the lifetime of the borrow of `self`.
+    pub fn reborrow(&self) -> Erased<'_> {
+        *self
+    }
+
+    /// Recover an erased reference from its FFI-safe representation.
+    ///
+    /// # Safety
+    /// `ffi` must have been created by `Erased::to_ffi`.
+    /// Since the FFI representation does not carry a lifetime, the caller must make sure the value is still alive for as long as the returned erased reference is used.
+    pub unsafe fn from_ffi(ffi: ErasedFfi) -> Erased<'static> {
+        Erased {
+            // Safety: From the safety comment `ffi` was created from an erased reference, so the pointer is not null.
+            ptr: NonNull::new_unchecked(ffi.ptr.cast_mut()),
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: None,
+            #[cfg(feature = "checked")]
+            is_static: false,
+        }
+    }
+
+    /// Get the pointer to the erased value as a `*const c_void`, for passing it to C APIs as a `const void*` user-data pointer.
+    /// The pointer is valid for as long as `'a` is alive.
+    pub fn as_c_void(&self) -> *const c_void {
+        self.ptr.as_ptr().cast_const().cast()
+    }
+
+    /// Get the pointer to the erased value as a `NonNull<T>`, for passing it to APIs that take a typed pointer.
+    ///
+    /// The pointer is valid for reads for as long as `'a` is alive, and it must not be written to.
+    /// Unlike the returned reference of `Erased::get`, this lifetime is not tracked by the compiler, so the caller must make sure the pointer is not used after `'a` ends.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn as_non_null_lifetime<T>(&self) -> NonNull<T> {
+        self.ptr.cast::<T>()
+    }
+
+    /// Recover an erased reference from a `*const c_void` user-data pointer, as passed back by a C callback.
+    ///
+    /// # Safety
+    /// `ptr` must be non-null, and must point to a value that stays alive and is not mutated for the lifetime `'a`, which is chosen by the caller.
+    /// Usually `ptr` was created using `Erased::as_c_void`, in which case `'a` must not outlive the lifetime of that erased reference.
+    pub unsafe fn from_c_void(ptr: *const c_void) -> Erased<'a> {
+        Erased {
+            // Safety: From the safety comment the pointer is not null.
+            ptr: NonNull::new_unchecked(ptr.cast_mut().cast()),
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: None,
+            #[cfg(feature = "checked")]
+            is_static: false,
+        }
+    }
+
+    /// Convert the erased reference into its FFI-safe representation, dropping the lifetime.
+    pub fn to_ffi(self) -> ErasedFfi {
+        ErasedFfi {
+            ptr: self.ptr.as_ptr().cast_const(),
+        }
+    }
+
+    /// Check whether this erased reference was created from a `T` using `Erased::new_checked`.
+    #[cfg(feature = "checked")]
+    pub fn is<T: 'static>(&self) -> bool {
+        self.matches_type_id(TypeId::of::<T>())
+    }
+
+    /// Check whether this erased reference was created using `Erased::new_checked` from a type with the `TypeId` `id`.
+    /// This can be used when the expected type is only known at runtime.
+    #[cfg(feature = "checked")]
+    pub fn matches_type_id(&self, id: TypeId) -> bool {
+        self.type_info.is_some_and(|info| info.id == id)
+    }
+
+    /// Get typed references to all values in `v`, if they were all created from a `T` using `Erased::new_checked`.
+    /// Returns `None` if any of the values is not a `T`, or was not created using `Erased::new_checked`.
+    ///
+    /// This can be used to get typed access to a heterogeneous collection that turns out to be homogeneous, for example after filtering it.
+    #[cfg(feature = "checked")]
+    pub fn as_homogeneous<T: 'static>(v: &[Erased<'a>]) -> Option<Vec<&'a T>> {
+        v.iter()
+            // Safety: The type id of the value matches, so it is a `T`.
+            .map(|erased| erased.is::<T>().then(|| unsafe { erased.get::<T>() }))
+            .collect()
+    }
+
     /// Get a reference to `T` back from the erased reference.
     ///
+    /// # Lifetimes
+    /// The returned reference is valid for the lifetime `'a` of the erased reference, the compiler checks that it is not used for longer than that.
+    /// The compiler can however **not** check the lifetime parameters of `T` itself.
+    /// If the erased reference was created from a `&'a Foo<'x>`, it must be recovered as `Foo<'x>`, not as `Foo<'static>` or any other lifetime.
+    /// For types without lifetime parameters, such as `u64` or `String`, this hazard does not exist.
+    ///
+    /// ```rust
+    /// use erased::Erased;
+    ///
+    /// let value = 5usize;
+    /// let inner: &usize = &value;
+    /// let erased = Erased::new(&inner);
+    ///
+    /// // Safety: Recovered as `&'x usize` with the same `'x` as `inner`, which is inferred here.
+    /// let recovered: &&usize = unsafe { erased.get::<&usize>() };
+    /// assert_eq!(**recovered, 5);
+    ///
+    /// // Recovering as `&'static usize` would compile, but is unsound, since `value` does not live for `'static`:
+    /// // let wrong: &&'static usize = unsafe { erased.get::<&'static usize>() };
+    /// ```
+    ///
     /// # Safety
     /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
     /// Pay specific attention that any lifetime parameters of `T` match.
@@ -42,6 +329,304 @@ impl<'a> Erased<'a> {
         // Safety: From the safety comment the `T` matches the `T` this erased reference was created with. The reference is still valid since its lifetime `'a` is still alive.
         self.ptr.cast::<T>().as_ref()
     }
+
+    /// Get a handle to `T`, which performs the cast to `T` when it is dereferenced.
+    /// This allows accessing the value repeatedly without repeating `T` and the unsafe block at every access.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn lazy_get<T>(&self) -> LazyErased<'a, T> {
+        // Safety: Guaranteed by the safety comment of this function.
+        LazyErased::new(*self)
+    }
+
+    /// Get a reference to `T` back from an erased reference that was created using `Erased::new_checked`.
+    ///
+    /// # Panics
+    /// Panics if the erased reference was not created from a `T` using `Erased::new_checked`, naming both the recorded and the requested type.
+    #[cfg(feature = "checked")]
+    pub fn get_checked<T: 'static>(&self) -> &'a T {
+        crate::checked::assert_type::<T>(self.type_info);
+        // Safety: The type id matches, so the value is a `T`.
+        unsafe { self.get::<T>() }
+    }
+
+    /// Get a reference to `T` back from the erased reference, with an explicitly named lifetime `'b` that is bounded by `'a`.
+    ///
+    /// This is the same as `Erased::get`, but makes the lifetime of the returned reference explicit.
+    /// The bounds `'a: 'b` and `T: 'b` are checked by the compiler, so the returned reference can not outlive the erased reference:
+    /// ```rust,compile_fail
+    /// use erased::Erased;
+    ///
+    /// let recovered: &'static usize = {
+    ///     let value = 5usize;
+    ///     let erased = Erased::new(&value);
+    ///     // Safety: Matches the type of `value` exactly, which was used to create the `erased` value
+    ///     unsafe { erased.get_with_lifetime::<'static, usize>() }
+    /// };
+    /// ```
+    ///
+    /// See the documentation of `Erased::get` for the lifetime parameters of `T`, which are **not** checked by the compiler.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get_with_lifetime<'b, T: 'b>(self) -> &'b T
+    where
+        'a: 'b,
+    {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.get::<T>()
+    }
+
+    /// Borrow the `T` behind the erased reference for the duration of the closure `f`.
+    ///
+    /// The reference passed to `f` is only valid inside the closure, the higher-ranked lifetime prevents it from being smuggled out:
+    /// ```rust,compile_fail
+    /// use erased::Erased;
+    ///
+    /// let erased = Erased::new(&5usize);
+    /// // Safety: Matches the type used to create `erased`
+    /// let escaped: &usize = unsafe { erased.borrow_scoped::<usize, _>(|v| v) };
+    /// ```
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn borrow_scoped<T, R>(&self, f: impl for<'x> FnOnce(&'x T) -> R) -> R {
+        // Safety: Guaranteed by the safety comment of this function.
+        f(self.get::<T>())
+    }
+
+    /// Get a reader over the bytes of the `T` behind the erased reference.
+    ///
+    /// This is intended for erased byte buffers such as `[u8; N]`, so they can be fed into `Read`-based APIs.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// Additionally, all bytes of `T` must be initialized, so `T` may not contain any padding.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn as_reader<T>(&self) -> impl Read + 'a {
+        // Safety: Guaranteed by the safety comment of this function.
+        Cursor::new(self.bytes::<T>())
+    }
+
+    /// Get an erased reference to a field of the `Parent` behind the erased reference.
+    /// The `offset` is the byte offset of the field in `Parent`, which should be computed using `std::mem::offset_of!`.
+    ///
+    /// # Safety
+    /// The generic argument `Parent` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `Parent` match.
+    ///
+    /// `offset` must be the offset of a field of type `Field` in `Parent`.
+    pub unsafe fn field<Parent, Field>(&self, offset: usize) -> Erased<'a> {
+        debug_assert!(offset + size_of::<Field>() <= size_of::<Parent>());
+        // Safety: From the safety comment the offset is the offset of a field in `Parent`, so it stays in bounds of the referenced value.
+        self.at_offset(offset)
+    }
+
+    /// Get erased references to two fields of the value behind the erased reference, of types `A` and `B`.
+    /// The `off_a` and `off_b` are the byte offsets of the fields, which should be computed using `std::mem::offset_of!`.
+    ///
+    /// Since both returned references are shared, the fields are allowed to overlap.
+    /// Note that this would not be the case for mutable references, which must be disjoint.
+    ///
+    /// # Safety
+    /// `off_a` must be the offset of a field of type `A`, and `off_b` must be the offset of a field of type `B` in the type that was used to create this erased reference in `Erased::new`.
+    pub unsafe fn split_fields<A, B>(
+        &self,
+        off_a: usize,
+        off_b: usize,
+    ) -> (Erased<'a>, Erased<'a>) {
+        // Safety: From the safety comment the offsets are offsets of fields, so they stay in bounds of the referenced value.
+        (self.at_offset(off_a), self.at_offset(off_b))
+    }
+
+    /// Get an erased reference to the value at `offset` bytes from this erased reference.
+    ///
+    /// # Safety
+    /// `offset` must stay in bounds of the referenced value.
+    unsafe fn at_offset(&self, offset: usize) -> Erased<'a> {
+        Self {
+            // Safety: Guaranteed by the safety comment of this function.
+            ptr: self.ptr.byte_add(offset),
+            phantom: PhantomData,
+            #[cfg(feature = "checked")]
+            type_info: None,
+            #[cfg(feature = "checked")]
+            is_static: self.is_static,
+        }
+    }
+
+    /// Iterate over a chain of erased references, starting at this one, where `next` maps each node of type `T` to the erased reference to its successor.
+    /// The iterator stops when `next` returns `None`.
+    /// This can be used to traverse erased linked lists or the spine of an erased tree.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference, **and** every erased reference returned by `next`, in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn follow<T: 'a>(
+        &self,
+        next: impl Fn(&'a T) -> Option<Erased<'a>>,
+    ) -> impl Iterator<Item = Erased<'a>> {
+        // Safety: From the safety comment every erased reference in the chain was created from a `T`.
+        iter::successors(Some(*self), move |node| next(unsafe { node.get::<T>() }))
+    }
+
+    /// Iterate over the bytes of the `T` behind the erased reference, without allocating.
+    /// This can be used to stream the bytes into a hasher or checksum.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// Additionally, all bytes of `T` must be initialized, so `T` may not contain any padding.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn byte_iter<T>(&self) -> impl Iterator<Item = u8> + 'a {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.bytes::<T>().iter().copied()
+    }
+
+    /// Get a view of the bytes of the value behind the erased reference, which does not allow recovering the typed value.
+    /// The number of bytes in the view is the size of `layout`.
+    ///
+    /// # Safety
+    /// The value behind the erased reference must be at least `layout.size()` bytes large, and those bytes must be initialized.
+    pub unsafe fn bytes_view(&self, layout: Layout) -> ErasedBytes<'a> {
+        // Safety: From the safety comment the first `layout.size()` bytes are initialized.
+        ErasedBytes::new(std::slice::from_raw_parts(
+            self.ptr.cast::<u8>().as_ptr(),
+            layout.size(),
+        ))
+    }
+
+    /// Copy the bytes of the `T` behind the erased reference into a new heap allocated buffer.
+    ///
+    /// Unlike the reference itself, the returned bytes are owned and can outlive `'a`.
+    /// They should be treated as opaque plain old data, for example for serialization.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `Erased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// Additionally, all bytes of `T` must be initialized, so `T` may not contain any padding.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn to_boxed_bytes<T>(&self) -> Box<[u8]> {
+        // Safety: Guaranteed by the safety comment of this function.
+        Box::from(self.bytes::<T>())
+    }
+
+    /// Check whether the value behind the erased reference starts with the magic number `MAGIC`.
+    /// The first 4 bytes are read as a `u32` in native endianness.
+    ///
+    /// This is a cheap validity check before recovering the typed value, for types that start with a known magic number.
+    /// It can catch gross type confusion, but a matching magic number does not prove that the value has the expected type.
+    ///
+    /// # Safety
+    /// The value behind the erased reference must be at least 4 bytes large, and its first 4 bytes must be initialized.
+    pub unsafe fn check_magic<const MAGIC: u32>(&self) -> bool {
+        // Safety: From the safety comment the first 4 bytes are initialized. The read is unaligned since the value may have a lower alignment.
+        self.ptr.cast::<u32>().read_unaligned() == MAGIC
+    }
+
+    /// Read the first bytes of the value behind the erased reference as a big endian integer `T`, such as a network-order integer in a wire-format buffer.
+    ///
+    /// # Safety
+    /// The value behind the erased reference must be at least `size_of::<T>()` bytes large, and those bytes must be initialized.
+    #[cfg(feature = "prim_int")]
+    pub unsafe fn read_int_be<T: PrimInt>(&self) -> T {
+        // Safety: From the safety comment the first `size_of::<T>()` bytes are initialized. The read is unaligned since the value may have a lower alignment.
+        T::from_be(self.ptr.cast::<T>().read_unaligned())
+    }
+
+    /// Read the first bytes of the value behind the erased reference as a little endian integer `T`.
+    ///
+    /// # Safety
+    /// The value behind the erased reference must be at least `size_of::<T>()` bytes large, and those bytes must be initialized.
+    #[cfg(feature = "prim_int")]
+    pub unsafe fn read_int_le<T: PrimInt>(&self) -> T {
+        // Safety: From the safety comment the first `size_of::<T>()` bytes are initialized. The read is unaligned since the value may have a lower alignment.
+        T::from_le(self.ptr.cast::<T>().read_unaligned())
+    }
+
+    /// Find the candidate alignment with the largest alignment that the erased pointer satisfies, and return its index in `aligns`.
+    /// Candidates that are not a power of two are never satisfied.
+    ///
+    /// This can be used as a heuristic when guessing the type of the erased value among a known set of types.
+    /// Note that a pointer satisfying an alignment does not prove that it points to a value of a type with that alignment.
+    pub fn best_aligned_candidate(&self, aligns: &[usize]) -> Option<usize> {
+        let addr = self.ptr.as_ptr().addr();
+        aligns
+            .iter()
+            .enumerate()
+            .filter(|(_, align)| align.is_power_of_two() && addr.is_multiple_of(**align))
+            .fold(
+                None,
+                |best: Option<(usize, usize)>, (i, &align)| match best {
+                    Some((_, best_align)) if best_align >= align => best,
+                    _ => Some((i, align)),
+                },
+            )
+            .map(|(i, _)| i)
+    }
+
+    /// Check whether the erased reference points into the allocation of `other`, using the layout stored in the box.
+    ///
+    /// This is intended for debug assertions, to check that erased references point where they are expected to.
+    pub fn points_into(&self, other: &ErasedBox) -> bool {
+        self.overlaps(other.ptr(), other.layout().size())
+    }
+
+    /// Check whether the address of the erased reference lies in the allocation `[other_base, other_base + other_size)`.
+    ///
+    /// This can be used in debug builds to check that no erased reference points into an allocation that is about to be freed.
+    pub fn overlaps(&self, other_base: NonNull<()>, other_size: usize) -> bool {
+        self.is_in_range(other_base, other_size)
+    }
+
+    /// Assert that the erased reference points into the allocation `[base, base + size)`.
+    ///
+    /// This helps catch erased references that were accidentally created from a different allocation than expected.
+    /// The check is only performed in debug builds.
+    pub fn assert_provenance_of(&self, base: NonNull<()>, size: usize) {
+        debug_assert!(
+            self.is_in_range(base, size),
+            "Erased reference at {:p} does not point into the allocation at {:p} of size {}",
+            self.ptr,
+            base,
+            size
+        );
+    }
+
+    /// Check whether the address of the erased reference lies in `[base, base + size)`.
+    fn is_in_range(&self, base: NonNull<()>, size: usize) -> bool {
+        let addr = self.ptr.as_ptr().addr();
+        let base = base.as_ptr().addr();
+        addr >= base && addr - base < size
+    }
+
+    /// Get the bytes of the `T` behind the erased reference.
+    ///
+    /// # Safety
+    /// `T` must match the type this erased reference was created with, and all bytes of `T` must be initialized.
+    unsafe fn bytes<T>(&self) -> &'a [u8] {
+        // Safety: From the safety comment the pointer points to a valid `T`, which consists of `size_of::<T>()` initialized bytes.
+        self.bytes_view(Layout::new::<T>()).as_bytes()
+    }
 }
 
 impl<'a, T> From<&'a T> for Erased<'a> {
@@ -52,7 +637,16 @@ impl<'a, T> From<&'a T> for Erased<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::Erased;
+    use crate::{Erased, ErasedBox};
+    use std::alloc::Layout;
+    #[cfg(feature = "checked")]
+    use std::any::TypeId;
+    use std::cell::OnceCell;
+    use std::ffi::c_void;
+    use std::io::Read;
+    use std::mem::offset_of;
+    use std::ptr::NonNull;
+    use std::sync::Mutex;
 
     #[test]
     fn basic_test() {
@@ -80,4 +674,531 @@ mod tests {
         assert_eq!(unsafe { *vec[0].get::<u64>() }, 5);
         assert_eq!(unsafe { *vec[1].get::<&'static str>() }, "Hello World");
     }
+
+    #[test]
+    fn assume_static_test() {
+        static GREETING: &str = "Hello World";
+
+        fn store(erased: Erased<'_>) -> Erased<'static> {
+            // Safety: Only called with erased references to statics.
+            unsafe { erased.assume_static() }
+        }
+
+        let erased = store(Erased::new(&GREETING));
+        assert_eq!(*unsafe { erased.get::<&str>() }, "Hello World");
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn promote_to_static_test() {
+        static VALUES: (u32, u64) = (5, 42);
+        let local = 5u32;
+
+        let erased: [Erased<'_>; 2] = [Erased::new_static(&VALUES), Erased::new(&local)];
+        let promoted = erased[0].promote_to_static().unwrap();
+        assert_eq!(unsafe { promoted.get::<(u32, u64)>() }, &(5, 42));
+        assert!(erased[1].promote_to_static().is_none());
+
+        let field = unsafe { erased[0].field::<(u32, u64), u64>(offset_of!((u32, u64), 1)) };
+        let field = field.promote_to_static().unwrap();
+        assert_eq!(unsafe { *field.get::<u64>() }, 42);
+
+        let mut value = 5u32;
+        assert!(Erased::from_exclusive(&mut value)
+            .promote_to_static()
+            .is_none());
+    }
+
+    #[test]
+    fn from_slice_index_test() {
+        let values = [String::from("Hello"), String::from("World")];
+        let erased = Erased::from_slice_index(&values, 1).unwrap();
+        assert_eq!(unsafe { erased.get::<String>() }, "World");
+        assert!(Erased::from_slice_index(&values, 2).is_none());
+        assert!(Erased::from_slice_index::<u8>(&[], 0).is_none());
+    }
+
+    #[test]
+    fn from_mutex_guard_test() {
+        let numbers = Mutex::new(vec![1u32, 2, 3]);
+        let name = Mutex::new(String::from("Hello World"));
+        {
+            let numbers_guard = numbers.lock().unwrap();
+            let name_guard = name.lock().unwrap();
+            let erased = [
+                Erased::from_mutex_guard(&numbers_guard),
+                Erased::from_mutex_guard(&name_guard),
+            ];
+            assert_eq!(unsafe { erased[0].get::<Vec<u32>>() }, &[1, 2, 3]);
+            assert_eq!(unsafe { erased[1].get::<String>() }, "Hello World");
+        }
+        assert!(numbers.try_lock().is_ok());
+    }
+
+    #[test]
+    fn get_with_lifetime_test() {
+        fn shorten<'b, 'a: 'b>(erased: &Erased<'a>) -> &'b usize {
+            unsafe { erased.get_with_lifetime::<'b, usize>() }
+        }
+
+        let value = 5usize;
+        let erased = Erased::new(&value);
+        assert_eq!(*shorten(&erased), 5);
+
+        let inner = &value;
+        let erased = Erased::new(&inner);
+        let recovered = unsafe { erased.get_with_lifetime::<&usize>() };
+        assert_eq!(**recovered, 5);
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn get_checked_test() {
+        let value = String::from("Hello World");
+        let erased = Erased::new_checked(&value);
+        assert_eq!(erased.get_checked::<String>(), "Hello World");
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "Erased value of type `u64` was recovered as type `u32`")]
+    fn get_checked_mismatch_test() {
+        Erased::new_checked(&5u64).get_checked::<u32>();
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    #[should_panic(expected = "not created using `new_checked`")]
+    fn get_checked_unchecked_test() {
+        Erased::new(&5u64).get_checked::<u64>();
+    }
+
+    #[test]
+    #[cfg(not(feature = "checked"))]
+    fn unchecked_size_test() {
+        assert_eq!(size_of::<Erased>(), size_of::<NonNull<()>>());
+        assert_eq!(size_of::<crate::ErasedMut>(), size_of::<NonNull<()>>());
+    }
+
+    #[test]
+    fn non_static_unchecked_test() {
+        struct Borrowed<'b>(&'b str);
+
+        let s = String::from("Hello World");
+        let value = Borrowed(&s);
+        let erased = Erased::new(&value);
+        assert_eq!(unsafe { erased.get::<Borrowed>() }.0, "Hello World");
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn matches_type_id_test() {
+        let erased = Erased::new_checked(&5usize);
+        let runtime_ids = [TypeId::of::<u32>(), TypeId::of::<usize>()];
+        assert!(!erased.matches_type_id(runtime_ids[0]));
+        assert!(erased.matches_type_id(runtime_ids[1]));
+        assert!(erased.is::<usize>());
+
+        let unchecked = Erased::new(&5usize);
+        assert!(!unchecked.matches_type_id(runtime_ids[1]));
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn fingerprint_test() {
+        let values = [5u32, 5u32];
+        let a = Erased::new_checked(&values[0]);
+        assert_eq!(
+            a.fingerprint(),
+            Erased::new_checked(&values[0]).fingerprint()
+        );
+        assert_ne!(
+            a.fingerprint(),
+            Erased::new_checked(&values[1]).fingerprint()
+        );
+        assert_ne!(a.fingerprint(), Erased::new_checked(&values).fingerprint());
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn as_homogeneous_test() {
+        let values = [1u32, 2, 3];
+        let homogeneous: Vec<Erased> = values.iter().map(Erased::new_checked).collect();
+        assert_eq!(
+            Erased::as_homogeneous::<u32>(&homogeneous),
+            Some(vec![&1, &2, &3])
+        );
+        assert_eq!(Erased::as_homogeneous::<u64>(&homogeneous), None);
+
+        let mut mixed = homogeneous.clone();
+        mixed.push(Erased::new_checked(&"Hello World"));
+        assert_eq!(Erased::as_homogeneous::<u32>(&mixed), None);
+    }
+
+    #[test]
+    fn reborrow_test() {
+        fn len(erased: Erased<'_>) -> usize {
+            unsafe { erased.get::<String>() }.len()
+        }
+
+        let value = String::from("Hello");
+        let erased = Erased::new(&value);
+        assert_eq!(len(erased.reborrow()), 5);
+        assert_eq!(unsafe { erased.get::<String>() }, "Hello");
+    }
+
+    #[test]
+    #[cfg(feature = "prim_int")]
+    fn read_int_test() {
+        let bytes = [0x12u8, 0x34, 0x56, 0x78, 0x9a];
+        let erased = Erased::new(&bytes);
+        assert_eq!(unsafe { erased.read_int_be::<u32>() }, 0x12345678);
+        assert_eq!(unsafe { erased.read_int_le::<u32>() }, 0x78563412);
+
+        let tail: &[u8; 4] = bytes[1..].try_into().unwrap();
+        let unaligned = Erased::new(tail);
+        assert_eq!(unsafe { unaligned.read_int_be::<u16>() }, 0x3456);
+        assert_eq!(
+            unsafe { unaligned.read_int_le::<i32>() },
+            0x9a785634u32 as i32
+        );
+    }
+
+    #[test]
+    fn from_exclusive_test() {
+        let mut value = 5usize;
+        {
+            let erased = Erased::from_exclusive(&mut value);
+            assert_eq!(unsafe { *erased.get::<usize>() }, 5);
+        }
+        value = 42;
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn from_once_test() {
+        let cell = OnceCell::new();
+        assert!(Erased::from_once(&cell).is_none());
+
+        cell.set(5usize).unwrap();
+        let erased = Erased::from_once(&cell).unwrap();
+        assert_eq!(unsafe { *erased.get::<usize>() }, 5);
+    }
+
+    #[test]
+    fn narrow_test() {
+        struct Short<'b> {
+            erased: Vec<Erased<'b>>,
+        }
+
+        let long: Erased<'static> = Erased::new(&5usize);
+        let value = 42usize;
+        let mut short = Short {
+            erased: vec![Erased::new(&value)],
+        };
+        short.erased.push(long.narrow());
+        assert_eq!(unsafe { *short.erased[1].get::<usize>() }, 5);
+    }
+
+    #[test]
+    fn ffi_test() {
+        let value = String::from("Hello World");
+        let ffi = Erased::new(&value).to_ffi();
+        let erased = unsafe { Erased::from_ffi(ffi) };
+        assert_eq!(unsafe { erased.get::<String>() }, "Hello World");
+    }
+
+    #[test]
+    fn as_c_void_test() {
+        extern "C" fn callback(user_data: *const c_void) -> u64 {
+            unsafe { *user_data.cast::<u64>() }
+        }
+
+        let erased = Erased::new(&42u64);
+        assert_eq!(callback(erased.as_c_void()), 42);
+    }
+
+    #[test]
+    fn as_non_null_lifetime_test() {
+        let value = String::from("Hello World");
+        let erased = Erased::new(&value);
+        let ptr = unsafe { erased.as_non_null_lifetime::<String>() };
+        assert_eq!(ptr, NonNull::from(&value));
+        assert_eq!(unsafe { ptr.as_ref() }, "Hello World");
+    }
+
+    #[test]
+    fn from_c_void_test() {
+        extern "C" fn call_with(
+            user_data: *const c_void,
+            f: extern "C" fn(*const c_void) -> u64,
+        ) -> u64 {
+            f(user_data)
+        }
+        extern "C" fn callback(user_data: *const c_void) -> u64 {
+            let erased = unsafe { Erased::from_c_void(user_data) };
+            unsafe { *erased.get::<u64>() }
+        }
+
+        let erased = Erased::new(&42u64);
+        assert_eq!(call_with(erased.as_c_void(), callback), 42);
+    }
+
+    #[test]
+    fn borrow_scoped_test() {
+        let erased = Erased::new(&5usize);
+        let doubled = unsafe { erased.borrow_scoped::<usize, _>(|v| *v * 2) };
+        assert_eq!(doubled, 10);
+    }
+
+    #[test]
+    fn as_reader_test() {
+        let buffer: [u8; 32] = std::array::from_fn(|i| i as u8);
+        let erased = Erased::new(&buffer);
+
+        let mut read = Vec::new();
+        unsafe { erased.as_reader::<[u8; 32]>() }
+            .read_to_end(&mut read)
+            .unwrap();
+        assert_eq!(read, buffer);
+    }
+
+    #[test]
+    fn byte_iter_test() {
+        let value = 0x0102_0304u32;
+        let erased = Erased::new(&value);
+
+        let checksum = unsafe { erased.byte_iter::<u32>() }.fold(0u8, u8::wrapping_add);
+        assert_eq!(checksum, 10);
+        assert_eq!(unsafe { erased.byte_iter::<u32>() }.count(), 4);
+    }
+
+    #[test]
+    fn bytes_view_test() {
+        let value = [1u16, 2];
+        let erased = Erased::new(&value);
+        let view = unsafe { erased.bytes_view(Layout::new::<[u16; 2]>()) };
+        assert_eq!(view.len(), 4);
+        assert_eq!(
+            view.as_bytes(),
+            [1u16.to_ne_bytes(), 2u16.to_ne_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn to_boxed_bytes_test() {
+        let value = Box::new(0x0123_4567_89ab_cdefu64);
+        let bytes = unsafe { Erased::new(&*value).to_boxed_bytes::<u64>() };
+        drop(value);
+
+        assert_eq!(
+            u64::from_ne_bytes((*bytes).try_into().unwrap()),
+            0x0123_4567_89ab_cdef
+        );
+    }
+
+    #[test]
+    fn check_magic_test() {
+        #[repr(C)]
+        struct Header {
+            magic: u32,
+            version: u16,
+        }
+
+        const MAGIC: u32 = u32::from_ne_bytes(*b"ERSD");
+        let header = Header {
+            magic: MAGIC,
+            version: 1,
+        };
+        let erased = Erased::new(&header);
+        assert!(unsafe { erased.check_magic::<MAGIC>() });
+        assert!(!unsafe { erased.check_magic::<0xDEAD_BEEF>() });
+        assert_eq!(unsafe { erased.get::<Header>() }.version, 1);
+
+        let bytes = *b"ELF!";
+        assert!(!unsafe { Erased::new(&bytes).check_magic::<MAGIC>() });
+    }
+
+    #[test]
+    fn best_aligned_candidate_test() {
+        #[repr(align(8))]
+        struct Aligned([u8; 16]);
+        let buffer = Aligned([0; 16]);
+
+        let aligned = Erased::new(&buffer.0[0]);
+        assert_eq!(aligned.best_aligned_candidate(&[2, 8, 4]), Some(1));
+
+        let misaligned = Erased::new(&buffer.0[4]);
+        assert_eq!(misaligned.best_aligned_candidate(&[2, 8, 4]), Some(2));
+        assert_eq!(misaligned.best_aligned_candidate(&[8, 3]), None);
+        assert_eq!(misaligned.best_aligned_candidate(&[]), None);
+    }
+
+    #[test]
+    fn field_test() {
+        struct Parent {
+            a: u8,
+            b: String,
+        }
+
+        let parent = Box::new(Parent {
+            a: 5,
+            b: String::from("Hello World"),
+        });
+        let erased = Erased::new(&*parent);
+
+        let a = unsafe { erased.field::<Parent, u8>(offset_of!(Parent, a)) };
+        let b = unsafe { erased.field::<Parent, String>(offset_of!(Parent, b)) };
+        assert_eq!(unsafe { *a.get::<u8>() }, 5);
+        assert_eq!(unsafe { b.get::<String>() }, "Hello World");
+    }
+
+    #[test]
+    fn follow_test() {
+        struct Node<'a> {
+            value: u32,
+            next: Option<Erased<'a>>,
+        }
+
+        let last = Node {
+            value: 3,
+            next: None,
+        };
+        let middle = Node {
+            value: 2,
+            next: Some(Erased::new(&last)),
+        };
+        let first = Node {
+            value: 1,
+            next: Some(Erased::new(&middle)),
+        };
+
+        let values: Vec<u32> = unsafe {
+            Erased::new(&first)
+                .follow::<Node>(|node| node.next)
+                .map(|erased| erased.get::<Node>().value)
+                .collect()
+        };
+        assert_eq!(values, [1, 2, 3]);
+        assert_eq!(
+            unsafe { Erased::new(&last).follow::<Node>(|node| node.next) }.count(),
+            1
+        );
+    }
+
+    #[test]
+    fn split_fields_test() {
+        struct Parent {
+            a: u8,
+            b: String,
+        }
+
+        let parent = Box::new(Parent {
+            a: 5,
+            b: String::from("Hello World"),
+        });
+        let erased = Erased::new(&*parent);
+
+        let (a, b) = unsafe {
+            erased.split_fields::<u8, String>(offset_of!(Parent, a), offset_of!(Parent, b))
+        };
+        assert_eq!(unsafe { *a.get::<u8>() }, 5);
+        assert_eq!(unsafe { b.get::<String>() }, "Hello World");
+    }
+
+    #[test]
+    fn points_into_test() {
+        let boxed = ErasedBox::new(Box::new([1u32, 2, 3]));
+        let inside = Erased::new(unsafe { &boxed.get_ref::<[u32; 3]>()[2] });
+        assert!(inside.points_into(&boxed));
+
+        let outside = Erased::new(&5u32);
+        assert!(!outside.points_into(&boxed));
+
+        unsafe { boxed.into_inner::<[u32; 3]>() };
+    }
+
+    #[test]
+    fn overlaps_test() {
+        let values = [1u32, 2, 3];
+        let base = NonNull::from(&values).cast();
+        let size = size_of_val(&values);
+        assert!(Erased::new(&values[0]).overlaps(base, size));
+        assert!(Erased::new(&values[2]).overlaps(base, size));
+        assert!(!Erased::new(&values[2]).overlaps(base, size_of::<u32>() * 2));
+        assert!(!Erased::new(&values[0]).overlaps(base, 0));
+
+        let other = 4u32;
+        assert!(!Erased::new(&other).overlaps(base, size));
+    }
+
+    #[test]
+    fn assert_provenance_of_test() {
+        let values = [1u32, 2, 3];
+        let base = NonNull::from(&values).cast();
+        Erased::new(&values[0]).assert_provenance_of(base, size_of_val(&values));
+        Erased::new(&values[2]).assert_provenance_of(base, size_of_val(&values));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "does not point into the allocation")]
+    fn assert_provenance_of_out_of_bounds_test() {
+        let values = [1u32, 2, 3];
+        let other = 4u32;
+        let base = NonNull::from(&values).cast();
+        Erased::new(&other).assert_provenance_of(base, size_of_val(&values));
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn derive_erased_fields_test() {
+        #[derive(crate::ErasedFields)]
+        struct Player<'n> {
+            name: &'n str,
+            health: u32,
+            position: (f32, f32),
+        }
+
+        let player = Player {
+            name: "Ferris",
+            health: 100,
+            position: (1.0, 2.0),
+        };
+        let fields = player.erased_fields();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(unsafe { *fields["name"].get::<&str>() }, "Ferris");
+        assert_eq!(unsafe { *fields["health"].get::<u32>() }, 100);
+        assert_eq!(
+            unsafe { *fields["position"].get::<(f32, f32)>() },
+            (1.0, 2.0)
+        );
+
+        #[derive(crate::ErasedFields)]
+        struct Pair(u8, String);
+
+        let pair = Pair(5, String::from("Hello"));
+        let fields = pair.erased_fields();
+        assert_eq!(unsafe { *fields["0"].get::<u8>() }, 5);
+        assert_eq!(unsafe { fields["1"].get::<String>() }, "Hello");
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn derive_erased_fields_raw_ident_test() {
+        #[derive(crate::ErasedFields)]
+        struct Token {
+            r#type: u8,
+            r#ref: &'static str,
+        }
+
+        let token = Token {
+            r#type: 3,
+            r#ref: "Hello",
+        };
+        let fields = token.erased_fields();
+        assert_eq!(unsafe { *fields["type"].get::<u8>() }, 3);
+        assert_eq!(unsafe { *fields["ref"].get::<&str>() }, "Hello");
+        assert!(!fields.contains_key("r#type"));
+    }
 }
@@ -0,0 +1,128 @@
+use crate::ErasedMut;
+use std::cell::{Cell, UnsafeCell};
+
+/// An erased mutable reference `&'a mut T`, which hands out at most one `ErasedMut` at a time, tracked by a runtime flag.
+///
+/// Erasing the mutable reference again while a previous erased mutable reference is still alive panics, instead of creating two aliasing mutable references.
+/// This is a safety net for code that loses track of its erased mutable references.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedRefCell;
+///
+/// let mut value = 5usize;
+/// let cell = ErasedRefCell::new(&mut value);
+///
+/// let mut guard = cell.erase_mut();
+/// assert!(cell.try_erase_mut().is_none());
+/// // Safety: Matches the type of `value` exactly, which was used to create the cell
+/// *unsafe { guard.erased().get::<usize>() } = 42;
+/// drop(guard);
+///
+/// assert!(cell.try_erase_mut().is_some());
+/// ```
+#[derive(Debug)]
+pub struct ErasedRefCell<'a> {
+    erased: UnsafeCell<ErasedMut<'a>>,
+    borrowed: Cell<bool>,
+}
+
+impl<'a> ErasedRefCell<'a> {
+    /// Create a new cell from a mutable reference to `T`
+    pub fn new<T>(t: &'a mut T) -> ErasedRefCell<'a> {
+        Self {
+            erased: UnsafeCell::new(ErasedMut::new(t)),
+            borrowed: Cell::new(false),
+        }
+    }
+
+    /// Check whether an erased mutable reference handed out by this cell is still alive
+    pub fn is_borrowed(&self) -> bool {
+        self.borrowed.get()
+    }
+
+    /// Get a guard for the erased mutable reference, if no other guard is alive.
+    /// Returns `None` if the erased mutable reference is already borrowed.
+    pub fn try_erase_mut(&self) -> Option<ErasedRefMut<'_, 'a>> {
+        if self.borrowed.replace(true) {
+            return None;
+        }
+        Some(ErasedRefMut { cell: self })
+    }
+
+    /// Get a guard for the erased mutable reference.
+    ///
+    /// # Panics
+    /// Panics if the erased mutable reference is already borrowed by another guard.
+    pub fn erase_mut(&self) -> ErasedRefMut<'_, 'a> {
+        self.try_erase_mut()
+            .expect("Erased mutable reference is already borrowed")
+    }
+}
+
+/// A guard for the erased mutable reference of an `ErasedRefCell`, created using `ErasedRefCell::erase_mut`.
+/// The cell can be borrowed again once the guard is dropped.
+#[derive(Debug)]
+pub struct ErasedRefMut<'b, 'a> {
+    cell: &'b ErasedRefCell<'a>,
+}
+
+impl ErasedRefMut<'_, '_> {
+    /// Get the erased mutable reference, which can't outlive the guard
+    pub fn erased(&mut self) -> ErasedMut<'_> {
+        // Safety: The borrow flag guarantees this is the only guard, and the guard is borrowed mutably ensuring exclusive access.
+        unsafe { (*self.cell.erased.get()).reborrow() }
+    }
+}
+
+impl Drop for ErasedRefMut<'_, '_> {
+    fn drop(&mut self) {
+        self.cell.borrowed.set(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedRefCell;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn basic_test() {
+        let mut value = vec![1u32, 2];
+        let cell = ErasedRefCell::new(&mut value);
+        {
+            let mut guard = cell.erase_mut();
+            assert!(cell.is_borrowed());
+            unsafe { guard.erased().get::<Vec<u32>>() }.push(3);
+        }
+        assert!(!cell.is_borrowed());
+        let mut guard = cell.erase_mut();
+        assert_eq!(unsafe { guard.erased().get_ref::<Vec<u32>>() }, &[1, 2, 3]);
+        drop(guard);
+        assert_eq!(value, [1, 2, 3]);
+    }
+
+    #[test]
+    fn double_erase_test() {
+        let mut value = 5usize;
+        let cell = ErasedRefCell::new(&mut value);
+        let mut first = cell.erase_mut();
+        assert!(cell.try_erase_mut().is_none());
+
+        let err = panic::catch_unwind(AssertUnwindSafe(|| {
+            cell.erase_mut();
+        }))
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().map(String::as_str),
+            Some("Erased mutable reference is already borrowed")
+        );
+
+        // The failed attempt does not release the first guard
+        assert!(cell.is_borrowed());
+        *unsafe { first.erased().get::<usize>() } = 42;
+        drop(first);
+        assert!(cell.try_erase_mut().is_some());
+        assert_eq!(value, 42);
+    }
+}
@@ -0,0 +1,104 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// An erased reference to a slice `&'a [T]`, which retains the length of the slice.
+///
+/// Unlike `ErasedUnsized`, the length of the slice can be read without knowing `T`.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedSlice;
+///
+/// let a = ErasedSlice::new(&[1u32, 2, 3]);
+/// let b = ErasedSlice::new(&[1u32, 2, 3]);
+/// assert_eq!(a.len(), 3);
+///
+/// // Safety: Both slices were created from a `[u32]`.
+/// assert!(unsafe { a.slice_eq::<u32>(&b) });
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ErasedSlice<'a> {
+    ptr: NonNull<()>,
+    len: usize,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> ErasedSlice<'a> {
+    /// Create a new erased reference from a slice `&'a [T]`
+    pub fn new<T>(t: &'a [T]) -> ErasedSlice<'a> {
+        Self {
+            ptr: NonNull::from(t).cast(),
+            len: t.len(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the number of elements in the slice
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the slice has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the slice `&'a [T]` back from the erased reference.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the element type `T` that was used to create this erased reference in `ErasedSlice::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get<T>(&self) -> &'a [T] {
+        // Safety: From the safety comment the `T` matches the `T` this erased reference was created with. The reference is still valid since its lifetime `'a` is still alive.
+        NonNull::slice_from_raw_parts(self.ptr.cast::<T>(), self.len).as_ref()
+    }
+
+    /// Compare the elements of this slice and `other` as `T`. Slices with different lengths are never equal.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the element type `T` that was used to create **both** erased references in `ErasedSlice::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn slice_eq<T: PartialEq>(&self, other: &ErasedSlice) -> bool {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.len == other.len && self.get::<T>() == other.get::<T>()
+    }
+}
+
+impl<'a, T> From<&'a [T]> for ErasedSlice<'a> {
+    fn from(value: &'a [T]) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedSlice;
+
+    #[test]
+    fn get_test() {
+        let strings = [String::from("Hello"), String::from("World")];
+        let erased = ErasedSlice::from(&strings[..]);
+        assert_eq!(erased.len(), 2);
+        assert!(!erased.is_empty());
+        assert_eq!(unsafe { erased.get::<String>() }.join(" "), "Hello World");
+    }
+
+    #[test]
+    fn slice_eq_test() {
+        let a = ErasedSlice::new(&[1u32, 2, 3]);
+        let b = ErasedSlice::new(&[1u32, 2, 3]);
+        let c = ErasedSlice::new(&[1u32, 2, 4]);
+        let d = ErasedSlice::new(&[1u32, 2]);
+        let empty = ErasedSlice::new::<u32>(&[]);
+
+        assert!(unsafe { a.slice_eq::<u32>(&b) });
+        assert!(!unsafe { a.slice_eq::<u32>(&c) });
+        assert!(!unsafe { a.slice_eq::<u32>(&d) });
+        assert!(!unsafe { d.slice_eq::<u32>(&a) });
+        assert!(unsafe { empty.slice_eq::<u32>(&ErasedSlice::new::<u32>(&[])) });
+    }
+}
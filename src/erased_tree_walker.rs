@@ -0,0 +1,181 @@
+use crate::Erased;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// The order in which an `ErasedTreeWalker` visits the nodes of a tree
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TraversalOrder {
+    /// Visit a node before its children, and visit all descendants of a child before its next sibling
+    DepthFirst,
+    /// Visit all nodes at one depth before any of the nodes at the next depth
+    BreadthFirst,
+}
+
+/// An iterator over the nodes of a tree of erased references, starting at the root.
+///
+/// The children of a node are enumerated by a closure, which is responsible for recovering the type of each node.
+/// This allows walking trees that consist of nodes of different types, such as the nodes of an AST.
+///
+/// Example:
+/// ```rust
+/// use erased::{Erased, ErasedTreeWalker, TraversalOrder};
+///
+/// struct Node(u32, Vec<Node>);
+///
+/// let tree = Node(1, vec![Node(2, vec![Node(4, vec![])]), Node(3, vec![])]);
+///
+/// // Safety: All nodes in the tree are a `Node`
+/// let walker = ErasedTreeWalker::new(Erased::new(&tree), TraversalOrder::BreadthFirst, |erased| {
+///     unsafe { erased.get::<Node>() }.1.iter().map(Erased::new).collect()
+/// });
+/// let values: Vec<u32> = walker.map(|erased| unsafe { erased.get::<Node>() }.0).collect();
+/// assert_eq!(values, [1, 2, 3, 4]);
+/// ```
+pub struct ErasedTreeWalker<'a, F> {
+    pending: VecDeque<Erased<'a>>,
+    order: TraversalOrder,
+    children: F,
+}
+
+impl<'a, F: FnMut(Erased<'a>) -> Vec<Erased<'a>>> ErasedTreeWalker<'a, F> {
+    /// Create a walker over the tree starting at `root`, visiting nodes in `order`.
+    /// `children` is called once for each visited node, and returns the children of that node in order.
+    pub fn new(root: Erased<'a>, order: TraversalOrder, children: F) -> ErasedTreeWalker<'a, F> {
+        Self {
+            pending: VecDeque::from([root]),
+            order,
+            children,
+        }
+    }
+}
+
+impl<'a, F: FnMut(Erased<'a>) -> Vec<Erased<'a>>> Iterator for ErasedTreeWalker<'a, F> {
+    type Item = Erased<'a>;
+
+    fn next(&mut self) -> Option<Erased<'a>> {
+        let node = self.pending.pop_front()?;
+        let children = (self.children)(node);
+        match self.order {
+            TraversalOrder::DepthFirst => {
+                for child in children.into_iter().rev() {
+                    self.pending.push_front(child);
+                }
+            }
+            TraversalOrder::BreadthFirst => self.pending.extend(children),
+        }
+        Some(node)
+    }
+}
+
+impl<F> fmt::Debug for ErasedTreeWalker<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErasedTreeWalker")
+            .field("pending", &self.pending)
+            .field("order", &self.order)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Erased, ErasedTreeWalker, TraversalOrder};
+
+    struct Node(u32, Vec<Node>);
+
+    fn tree() -> Node {
+        Node(
+            1,
+            vec![
+                Node(2, vec![Node(4, vec![]), Node(5, vec![])]),
+                Node(3, vec![Node(6, vec![])]),
+            ],
+        )
+    }
+
+    fn walk(tree: &Node, order: TraversalOrder) -> Vec<u32> {
+        ErasedTreeWalker::new(Erased::new(tree), order, |erased| {
+            unsafe { erased.get::<Node>() }
+                .1
+                .iter()
+                .map(Erased::new)
+                .collect()
+        })
+        .map(|erased| unsafe { erased.get::<Node>() }.0)
+        .collect()
+    }
+
+    #[test]
+    fn depth_first_test() {
+        assert_eq!(
+            walk(&tree(), TraversalOrder::DepthFirst),
+            [1, 2, 4, 5, 3, 6]
+        );
+    }
+
+    #[test]
+    fn breadth_first_test() {
+        assert_eq!(
+            walk(&tree(), TraversalOrder::BreadthFirst),
+            [1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn mixed_types_test() {
+        struct Function {
+            name: &'static str,
+            params: Vec<u32>,
+        }
+        struct Module {
+            functions: Vec<Function>,
+        }
+
+        let module = Module {
+            functions: vec![
+                Function {
+                    name: "main",
+                    params: vec![7],
+                },
+                Function {
+                    name: "add",
+                    params: vec![1, 2],
+                },
+            ],
+        };
+
+        fn children(erased: Erased<'_>) -> Vec<Erased<'_>> {
+            if erased.is::<Module>() {
+                let module = erased.get_checked::<Module>();
+                module.functions.iter().map(Erased::new_checked).collect()
+            } else if erased.is::<Function>() {
+                let function = erased.get_checked::<Function>();
+                function.params.iter().map(Erased::new_checked).collect()
+            } else {
+                vec![]
+            }
+        }
+        fn describe(erased: Erased<'_>) -> String {
+            if erased.is::<Module>() {
+                String::from("module")
+            } else if erased.is::<Function>() {
+                erased.get_checked::<Function>().name.to_string()
+            } else {
+                erased.get_checked::<u32>().to_string()
+            }
+        }
+
+        let root = Erased::new_checked(&module);
+        let depth_first: Vec<String> =
+            ErasedTreeWalker::new(root, TraversalOrder::DepthFirst, children)
+                .map(describe)
+                .collect();
+        assert_eq!(depth_first, ["module", "main", "7", "add", "1", "2"]);
+
+        let breadth_first: Vec<String> =
+            ErasedTreeWalker::new(root, TraversalOrder::BreadthFirst, children)
+                .map(describe)
+                .collect();
+        assert_eq!(breadth_first, ["module", "main", "add", "7", "1", "2"]);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::fat_ptr::FatPtr;
+use std::any;
+use std::mem;
+use std::ptr::NonNull;
+
+/// A box with an erased, possibly unsized type, such as `Box<[T]>` or `Box<dyn Trait>`.
+///
+/// Unlike `ErasedBox`, this retains the metadata of the box, such as the length of a slice or the vtable of a trait object.
+/// A box that holds a slice `[T]` also remembers that it holds a slice, so its length can be read using `slice_len`.
+///
+/// # Warning
+/// This type **leaks** the Box when it is dropped.
+/// To ensure that the Box is not leaked, call `into_inner` on it before it is dropped.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedUnsizedBox;
+///
+/// let b: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+/// let erased: ErasedUnsizedBox = ErasedUnsizedBox::new(b);
+///
+/// /// Safety: The type given to `into_inner` matches the type of `b`.
+/// let v: Box<[u32]> = unsafe { erased.into_inner::<[u32]>() };
+/// assert_eq!(*v, [1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct ErasedUnsizedBox {
+    ptr: FatPtr,
+    slice: bool,
+}
+
+impl ErasedUnsizedBox {
+    /// Create a new erased box from a `Box<T>`
+    pub fn new<T: ?Sized>(t: Box<T>) -> ErasedUnsizedBox {
+        Self {
+            ptr: FatPtr::new(NonNull::from(Box::leak(t))),
+            slice: Self::is_slice::<T>(),
+        }
+    }
+
+    /// Check whether `T` is a slice `[U]`.
+    ///
+    /// Only pointers to unsized types carry metadata, and of those only slices are named `[U]`, since `str`, trait objects and structs with an unsized field are not.
+    fn is_slice<T: ?Sized>() -> bool {
+        size_of::<NonNull<T>>() != size_of::<NonNull<()>>()
+            && any::type_name::<T>().starts_with('[')
+    }
+
+    /// Create a new erased box from a `Box<[T]>`, whose length can be read using `slice_len`.
+    /// This is `ErasedUnsizedBox::new`, for when it is known that the box holds a slice.
+    pub fn new_slice<T>(t: Box<[T]>) -> ErasedUnsizedBox {
+        Self {
+            ptr: FatPtr::new(NonNull::from(Box::leak(t))),
+            slice: true,
+        }
+    }
+
+    /// Get the number of elements in the slice in this box, if it holds a slice `[T]`.
+    /// The element type of the slice is not needed for this.
+    ///
+    /// Example:
+    /// ```rust
+    /// use erased::ErasedUnsizedBox;
+    ///
+    /// let erased = ErasedUnsizedBox::new_slice(vec![1u8, 2, 3].into_boxed_slice());
+    /// assert_eq!(erased.slice_len(), Some(3));
+    /// assert_eq!(ErasedUnsizedBox::new(Box::new(1u8)).slice_len(), None);
+    /// # unsafe { erased.into_inner::<[u8]>() };
+    ///
+    /// let erased = ErasedUnsizedBox::new::<[u8]>(vec![1u8, 2].into_boxed_slice());
+    /// assert_eq!(erased.slice_len(), Some(2));
+    /// # unsafe { erased.into_inner::<[u8]>() };
+    /// ```
+    pub fn slice_len(&self) -> Option<usize> {
+        // Safety: The box holds a `[T]`, which has the same metadata as a `[()]`. The pointer is not dereferenced.
+        self.slice.then(|| unsafe { self.ptr.cast::<[()]>() }.len())
+    }
+
+    /// Get a normal box `Box<T>` back from the erased box.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedUnsizedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match, including the lifetime bound of a trait object.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn into_inner<T: ?Sized>(self) -> Box<T> {
+        // Safety: From the safety comment the `T` matches the `T` this erased box was created with. The reference is unique since we consume `self`.
+        Box::from_raw(self.ptr.cast::<T>().as_ptr())
+    }
+
+    /// Get a reference to the value in this box.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedUnsizedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match, including the lifetime bound of a trait object.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get_ref<T: ?Sized>(&self) -> &T {
+        // Safety: From the safety comment the `T` matches the `T` this box was created with. The reference borrows self which owns the pointer, so its lifetime is valid.
+        self.ptr.cast::<T>().as_ref()
+    }
+
+    /// Get a mutable reference to the value in this box.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedUnsizedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match, including the lifetime bound of a trait object.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get_mut<T: ?Sized>(&mut self) -> &mut T {
+        // Safety: From the safety comment the `T` matches the `T` this box was created with. The reference borrows self which owns the pointer, so its lifetime is valid.
+        // Self is borrowed mutably ensuring exclusive access.
+        self.ptr.cast::<T>().as_mut()
+    }
+
+    /// Get the size in bytes of the value in this box, as given by `std::mem::size_of_val`.
+    /// For a slice this is the size of the elements times the length of the slice, for a trait object it is the size of the concrete type.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased box in `ErasedUnsizedBox::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match, including the lifetime bound of a trait object.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn size_of_val_as<T: ?Sized>(&self) -> usize {
+        // Safety: Guaranteed by the safety comment of this function.
+        mem::size_of_val(self.get_ref::<T>())
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for ErasedUnsizedBox {
+    fn from(value: Box<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedUnsizedBox;
+
+    #[test]
+    fn slice_test() {
+        let b: Box<[i32]> = vec![1, -2, 3, -4].into_boxed_slice();
+        let mut erased = ErasedUnsizedBox::new(b);
+        assert_eq!(unsafe { erased.get_ref::<[i32]>() }.len(), 4);
+
+        let slice = unsafe { erased.get_mut::<[i32]>() };
+        slice[1] = 2;
+        let recovered = unsafe { erased.into_inner::<[i32]>() };
+        assert_eq!(recovered.len(), 4);
+        assert_eq!(*recovered, [1, 2, 3, -4]);
+    }
+
+    #[test]
+    fn slice_len_test() {
+        let boxes = vec![
+            ErasedUnsizedBox::new_slice(Vec::<u64>::new().into_boxed_slice()),
+            ErasedUnsizedBox::new_slice(vec![1u8, 2, 3].into_boxed_slice()),
+            ErasedUnsizedBox::new_slice(vec![String::new(); 5].into_boxed_slice()),
+            ErasedUnsizedBox::new::<[u32]>(vec![1u32, 2].into_boxed_slice()),
+        ];
+        let lens: Vec<Option<usize>> = boxes.iter().map(ErasedUnsizedBox::slice_len).collect();
+        assert_eq!(lens, [Some(0), Some(3), Some(5), Some(2)]);
+        let total: usize = boxes.iter().filter_map(ErasedUnsizedBox::slice_len).sum();
+        assert_eq!(total, 10);
+
+        let mut boxes = boxes.into_iter();
+        unsafe {
+            boxes.next().unwrap().into_inner::<[u64]>();
+            boxes.next().unwrap().into_inner::<[u8]>();
+            boxes.next().unwrap().into_inner::<[String]>();
+            boxes.next().unwrap().into_inner::<[u32]>();
+        }
+    }
+
+    #[test]
+    fn slice_len_non_slice_test() {
+        let erased = ErasedUnsizedBox::new(Box::new([1u8, 2, 3]));
+        assert_eq!(erased.slice_len(), None);
+        unsafe { erased.into_inner::<[u8; 3]>() };
+
+        let erased = ErasedUnsizedBox::new::<str>(Box::from("Hello"));
+        assert_eq!(erased.slice_len(), None);
+        unsafe { erased.into_inner::<str>() };
+
+        let erased = ErasedUnsizedBox::new::<dyn Fn() -> u64>(Box::new(|| 5u64));
+        assert_eq!(erased.slice_len(), None);
+        drop(unsafe { erased.into_inner::<dyn Fn() -> u64>() });
+
+        let erased = ErasedUnsizedBox::new::<[[u8; 2]]>(vec![[1u8, 2]; 4].into_boxed_slice());
+        assert_eq!(erased.slice_len(), Some(4));
+        unsafe { erased.into_inner::<[[u8; 2]]>() };
+    }
+
+    #[test]
+    fn size_of_val_as_test() {
+        let erased = ErasedUnsizedBox::new::<[u32]>(vec![0u32; 7].into_boxed_slice());
+        assert_eq!(unsafe { erased.size_of_val_as::<[u32]>() }, 28);
+        unsafe { erased.into_inner::<[u32]>() };
+
+        let erased = ErasedUnsizedBox::new::<str>(Box::from("Hello"));
+        assert_eq!(unsafe { erased.size_of_val_as::<str>() }, 5);
+        unsafe { erased.into_inner::<str>() };
+
+        let erased = ErasedUnsizedBox::new::<dyn Fn() -> u64>(Box::new(|| 5u64));
+        assert_eq!(unsafe { erased.size_of_val_as::<dyn Fn() -> u64>() }, 0);
+        drop(unsafe { erased.into_inner::<dyn Fn() -> u64>() });
+
+        let value = [1u64, 2];
+        let erased = ErasedUnsizedBox::new::<dyn Fn() -> u64>(Box::new(move || value[0]));
+        assert_eq!(unsafe { erased.size_of_val_as::<dyn Fn() -> u64>() }, 16);
+        drop(unsafe { erased.into_inner::<dyn Fn() -> u64>() });
+    }
+
+    #[test]
+    fn dyn_test() {
+        let greeting = String::from("Hello World");
+        let vec: Vec<ErasedUnsizedBox> = vec![
+            ErasedUnsizedBox::new::<dyn Fn() -> usize>(Box::new(|| 42)),
+            ErasedUnsizedBox::new::<dyn Fn() -> usize>(Box::new(move || greeting.len())),
+        ];
+
+        let results: Vec<usize> = vec
+            .into_iter()
+            .map(|erased| unsafe { erased.into_inner::<dyn Fn() -> usize>() }())
+            .collect();
+        assert_eq!(results, [42, 11]);
+    }
+
+    #[test]
+    fn str_test() {
+        let erased = ErasedUnsizedBox::new(Box::<str>::from("Hello World"));
+        assert_eq!(&*unsafe { erased.into_inner::<str>() }, "Hello World");
+    }
+}
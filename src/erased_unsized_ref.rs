@@ -0,0 +1,127 @@
+use crate::fat_ptr::FatPtr;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// An erased reference to a possibly unsized value `&'a T`, such as a slice or a trait object.
+///
+/// Unlike `Erased`, this retains the metadata of the reference, such as the length of a slice or the vtable of a trait object.
+/// This makes it two pointers wide.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedUnsized;
+/// use std::fmt::Display;
+///
+/// let mut vec: Vec<ErasedUnsized> = Vec::new();
+/// vec.push(ErasedUnsized::new::<dyn Display>(&5u64));
+/// vec.push(ErasedUnsized::new::<dyn Display>(&"Hello World"));
+///
+/// // SAFETY: Above we only inserted `dyn Display` references into the vec.
+/// let strings: Vec<String> = vec.iter().map(|e| unsafe { e.get::<dyn Display>() }.to_string()).collect();
+/// assert_eq!(strings, ["5", "Hello World"]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ErasedUnsized<'a> {
+    ptr: FatPtr,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> ErasedUnsized<'a> {
+    /// Create a new erased reference from a reference to `T`
+    pub fn new<T: ?Sized>(t: &'a T) -> ErasedUnsized<'a> {
+        Self {
+            ptr: FatPtr::new(NonNull::from(t)),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get a reference to `T` back from the erased reference.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `ErasedUnsized::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match, including the lifetime bound of a trait object.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get<T: ?Sized>(&self) -> &'a T {
+        // Safety: From the safety comment the `T` matches the `T` this erased reference was created with. The reference is still valid since its lifetime `'a` is still alive.
+        self.ptr.cast::<T>().as_ref()
+    }
+
+    /// Get a reference to the trait object `Dyn` back from an erased reference created using `Erased::new_dyn`.
+    ///
+    /// # Safety
+    /// The generic argument `Dyn` of this function must match the `Dyn` that was used to create this erased reference in `Erased::new_dyn` exactly.
+    /// Pay specific attention that any lifetime parameters of `Dyn` match, including the lifetime bound of the trait object.
+    ///
+    /// It is **strongly recommended** to provide `Dyn` explicitly, even if it can be inferred. This is to make sure that the value of `Dyn` is not accidentally changed.
+    pub unsafe fn get_dyn<Dyn: ?Sized>(&self) -> &'a Dyn {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.get::<Dyn>()
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a T> for ErasedUnsized<'a> {
+    fn from(value: &'a T) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Erased, ErasedUnsized};
+    use std::fmt::Display;
+
+    #[test]
+    fn sized_test() {
+        let r1 = &5usize;
+        let erased = ErasedUnsized::new(r1);
+        assert_eq!(unsafe { erased.get::<usize>() }, r1);
+    }
+
+    #[test]
+    fn slice_test() {
+        let values: &[u32] = &[1, 2, 3];
+        let erased = ErasedUnsized::new(values);
+        let recovered = unsafe { erased.get::<[u32]>() };
+        assert_eq!(recovered.len(), 3);
+        assert_eq!(recovered, values);
+    }
+
+    #[test]
+    fn dyn_test() {
+        let greeting = String::from("Hello World");
+        let vec: Vec<ErasedUnsized> = vec![
+            ErasedUnsized::new::<dyn Display>(&42u8),
+            ErasedUnsized::new::<dyn Display>(&greeting),
+        ];
+
+        assert_eq!(unsafe { vec[0].get::<dyn Display>() }.to_string(), "42");
+        assert_eq!(
+            unsafe { vec[1].get::<dyn Display>() }.to_string(),
+            "Hello World"
+        );
+    }
+
+    #[test]
+    fn new_dyn_test() {
+        let greeting = String::from("Hello World");
+        let vec: Vec<ErasedUnsized> = vec![
+            Erased::new_dyn::<dyn Display>(&42u8),
+            Erased::new_dyn::<dyn Display>(&greeting),
+        ];
+
+        let strings: Vec<String> = vec
+            .iter()
+            .map(|erased| unsafe { erased.get_dyn::<dyn Display>() }.to_string())
+            .collect();
+        assert_eq!(strings, ["42", "Hello World"]);
+    }
+
+    #[test]
+    fn dyn_fn_test() {
+        let offset = 2;
+        let f = move || 40 + offset;
+        let erased = ErasedUnsized::new::<dyn Fn() -> u32>(&f);
+        assert_eq!(unsafe { erased.get::<dyn Fn() -> u32>() }(), 42);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::glue::Glue;
+use std::any::TypeId;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ptr::NonNull;
+
+/// A table of type-erased functions for a type `T`, which can be attached to an `ErasedBox` using `ErasedBox::with_vtable`.
+///
+/// An erased box with a vtable drops its value when it is dropped, and can be cloned, formatted, compared and hashed by dispatching to the implementations of `T`.
+/// The vtable is created once per type, usually in a `static`, so no global registry of types is needed.
+///
+/// Example:
+/// ```rust
+/// use erased::{ErasedBox, ErasedVtable};
+///
+/// static STRING_VTABLE: ErasedVtable = ErasedVtable::of::<String>();
+///
+/// let erased = ErasedBox::with_vtable(Box::new(String::from("Hello World")), &STRING_VTABLE);
+/// let clone = erased.try_clone().unwrap();
+/// assert_eq!(erased.vtable_eq(&clone), Some(true));
+/// assert_eq!(format!("{:?}", clone.vtable_debug().unwrap()), "\"Hello World\"");
+/// ```
+#[derive(Debug)]
+pub struct ErasedVtable {
+    pub(crate) glue: Glue,
+}
+
+/// The functions of an `ErasedVtable`, other than dropping the value.
+#[derive(Debug)]
+pub(crate) struct VtableFns {
+    pub(crate) type_id: TypeId,
+    pub(crate) clone: unsafe fn(NonNull<()>) -> NonNull<()>,
+    pub(crate) debug: unsafe fn(NonNull<()>, &mut fmt::Formatter<'_>) -> fmt::Result,
+    pub(crate) eq: unsafe fn(NonNull<()>, NonNull<()>) -> bool,
+    pub(crate) hash: unsafe fn(NonNull<()>, &mut dyn Hasher),
+}
+
+impl ErasedVtable {
+    /// Create the vtable for `T`
+    pub const fn of<T: Clone + fmt::Debug + PartialEq + Hash + 'static>() -> ErasedVtable {
+        /// Safety: `ptr` must point to a valid `T`.
+        unsafe fn clone<T: Clone>(ptr: NonNull<()>) -> NonNull<()> {
+            NonNull::from(Box::leak(Box::new(ptr.cast::<T>().as_ref().clone()))).cast()
+        }
+
+        /// Safety: `ptr` must point to a valid `T`.
+        unsafe fn debug<T: fmt::Debug>(
+            ptr: NonNull<()>,
+            f: &mut fmt::Formatter<'_>,
+        ) -> fmt::Result {
+            ptr.cast::<T>().as_ref().fmt(f)
+        }
+
+        /// Safety: `a` and `b` must both point to a valid `T`.
+        unsafe fn eq<T: PartialEq>(a: NonNull<()>, b: NonNull<()>) -> bool {
+            a.cast::<T>().as_ref() == b.cast::<T>().as_ref()
+        }
+
+        /// Safety: `ptr` must point to a valid `T`.
+        unsafe fn hash<T: Hash>(ptr: NonNull<()>, mut state: &mut dyn Hasher) {
+            ptr.cast::<T>().as_ref().hash(&mut state)
+        }
+
+        Self {
+            glue: Glue {
+                vtable: Some(VtableFns {
+                    type_id: TypeId::of::<T>(),
+                    clone: clone::<T>,
+                    debug: debug::<T>,
+                    eq: eq::<T>,
+                    hash: hash::<T>,
+                }),
+                ..Glue::owned::<T>()
+            },
+        }
+    }
+}
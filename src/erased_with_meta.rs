@@ -0,0 +1,93 @@
+use crate::Erased;
+
+/// An erased reference to a value `&'a T`, stored together with a metadata value `M`.
+///
+/// Example:
+/// ```rust
+/// use erased::ErasedWithMeta;
+///
+/// let mut vec: Vec<ErasedWithMeta<u8>> = Vec::new();
+/// vec.push(ErasedWithMeta::new(&5u64, 0));
+/// vec.push(ErasedWithMeta::new(&"Hello World", 1));
+///
+/// for item in &vec {
+///     match item.meta() {
+///         // SAFETY: Above we only inserted `u64`s with tag `0`.
+///         0 => assert_eq!(unsafe { *item.get::<u64>() }, 5),
+///         // SAFETY: Above we only inserted `&'static str`s with tag `1`.
+///         1 => assert_eq!(unsafe { *item.get::<&'static str>() }, "Hello World"),
+///         _ => unreachable!(),
+///     }
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ErasedWithMeta<'a, M> {
+    erased: Erased<'a>,
+    meta: M,
+}
+
+impl<'a, M> ErasedWithMeta<'a, M> {
+    /// Create a new erased reference from a reference to `T` and a metadata value
+    pub fn new<T>(t: &'a T, meta: M) -> ErasedWithMeta<'a, M> {
+        Self {
+            erased: Erased::new(t),
+            meta,
+        }
+    }
+
+    /// Get a reference to `T` back from the erased reference.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `ErasedWithMeta::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get<T>(&self) -> &'a T {
+        // Safety: Guaranteed by the safety comment of this function.
+        self.erased.get::<T>()
+    }
+
+    /// Get a reference to the metadata value
+    pub fn meta(&self) -> &M {
+        &self.meta
+    }
+
+    /// Get the erased reference without the metadata value
+    pub fn erased(&self) -> Erased<'a> {
+        self.erased
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ErasedWithMeta;
+
+    #[test]
+    fn basic_test() {
+        let r1 = &5usize;
+        let erased = ErasedWithMeta::new(r1, "usize");
+        assert_eq!(*erased.meta(), "usize");
+        assert_eq!(unsafe { erased.get::<usize>() }, r1);
+    }
+
+    #[test]
+    fn tagged_vec_test() {
+        let vec: Vec<ErasedWithMeta<u8>> = vec![
+            ErasedWithMeta::new(&5u64, 0),
+            ErasedWithMeta::new(&"Hello World", 1),
+            ErasedWithMeta::new(&7u64, 0),
+        ];
+
+        let mut sum = 0;
+        let mut strings = Vec::new();
+        for item in &vec {
+            match item.meta() {
+                0 => sum += unsafe { *item.get::<u64>() },
+                1 => strings.push(unsafe { *item.get::<&'static str>() }),
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(sum, 12);
+        assert_eq!(strings, ["Hello World"]);
+    }
+}
@@ -0,0 +1,29 @@
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+/// A pointer to a possibly unsized type, with its type erased.
+/// Unlike `NonNull<()>`, this retains the metadata of the pointer, such as the length of a slice or the vtable of a trait object.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct FatPtr([MaybeUninit<*const ()>; 2]);
+
+impl FatPtr {
+    /// Erase the type of `ptr`
+    pub(crate) fn new<T: ?Sized>(ptr: NonNull<T>) -> Self {
+        const {
+            assert!(size_of::<NonNull<T>>() <= size_of::<FatPtr>());
+        }
+        let mut words = [MaybeUninit::uninit(); 2];
+        // Safety: From the assertion above, the pointer fits in `words`, and the alignment of `words` matches that of a pointer.
+        unsafe { words.as_mut_ptr().cast::<NonNull<T>>().write(ptr) };
+        Self(words)
+    }
+
+    /// Get the typed pointer back.
+    ///
+    /// # Safety
+    /// `T` must match the `T` that was used to create this pointer in `FatPtr::new` exactly.
+    pub(crate) unsafe fn cast<T: ?Sized>(self) -> NonNull<T> {
+        // Safety: From the safety comment, `words` contains a `NonNull<T>`.
+        self.0.as_ptr().cast::<NonNull<T>>().read()
+    }
+}
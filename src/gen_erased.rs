@@ -0,0 +1,88 @@
+use crate::Erased;
+
+/// An erased reference to a value `&'a T`, stored together with a generation counter.
+///
+/// This is useful for slabs and arenas where slots are reused: a handle stores the generation of the slot it refers to,
+/// and accessing the value through a handle with a stale generation is detected instead of returning the new value of the slot.
+///
+/// Example:
+/// ```rust
+/// use erased::GenErased;
+///
+/// let slot = GenErased::new(&5u64, 1);
+///
+/// // SAFETY: The slot was created from a `u64`
+/// assert_eq!(unsafe { slot.get::<u64>(1) }, Some(&5));
+/// // SAFETY: The slot was created from a `u64`
+/// assert_eq!(unsafe { slot.get::<u64>(0) }, None);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct GenErased<'a> {
+    erased: Erased<'a>,
+    generation: u32,
+}
+
+impl<'a> GenErased<'a> {
+    /// Create a new erased reference from a reference to `T` and its generation
+    pub fn new<T>(t: &'a T, generation: u32) -> GenErased<'a> {
+        Self {
+            erased: Erased::new(t),
+            generation,
+        }
+    }
+
+    /// Get a reference to `T` back from the erased reference, if its generation is `expected_gen`.
+    /// Returns `None` if the generation does not match.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this erased reference in `GenErased::new` exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn get<T>(&self, expected_gen: u32) -> Option<&'a T> {
+        // Safety: Guaranteed by the safety comment of this function.
+        (self.generation == expected_gen).then(|| self.erased.get::<T>())
+    }
+
+    /// Get the generation of this erased reference
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Get the erased reference without the generation
+    pub fn erased(&self) -> Erased<'a> {
+        self.erased
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GenErased;
+
+    #[test]
+    fn basic_test() {
+        let r1 = &5usize;
+        let erased = GenErased::new(r1, 3);
+        assert_eq!(erased.generation(), 3);
+        assert_eq!(unsafe { erased.get::<usize>(3) }, Some(r1));
+    }
+
+    #[test]
+    fn stale_generation_test() {
+        let values = [String::from("old"), String::from("new")];
+        let mut slot = GenErased::new(&values[0], 0);
+        let handle = slot.generation();
+        assert_eq!(
+            unsafe { slot.get::<String>(handle) }.map(String::as_str),
+            Some("old")
+        );
+
+        // Reuse the slot for a new value
+        slot = GenErased::new(&values[1], slot.generation() + 1);
+        assert_eq!(unsafe { slot.get::<String>(handle) }, None);
+        assert_eq!(
+            unsafe { slot.get::<String>(slot.generation()) }.map(String::as_str),
+            Some("new")
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::erased_vtable::VtableFns;
+use std::any::Any;
+use std::mem;
+use std::ptr::NonNull;
+
+/// The functions to handle the value in an erased box, for a type `T`.
+/// A glue table is created once per type and constructor in a `static`, so an erased box only stores a reference to it.
+#[derive(Debug)]
+pub(crate) struct Glue {
+    /// Whether the value needs to be dropped, see `std::mem::needs_drop`
+    pub(crate) needs_drop: bool,
+    /// Drops the value and frees the allocation, for boxes that own their value
+    pub(crate) drop: Option<unsafe fn(NonNull<()>)>,
+    /// Calls the finalizer stored next to the value, for boxes created using `ErasedBox::with_finalizer`
+    pub(crate) finalize: Option<unsafe fn(NonNull<()>)>,
+    /// Clones, formats, compares and hashes the value, for boxes created using `ErasedBox::with_vtable`
+    pub(crate) vtable: Option<VtableFns>,
+    /// Turns the box into a `Box<dyn Any + Send + Sync>`, for boxes created using `ErasedBox::new_any_sync`
+    pub(crate) any: Option<AnyFn>,
+}
+
+/// A function that turns the box at a pointer into a `Box<dyn Any + Send + Sync>`.
+type AnyFn = unsafe fn(NonNull<()>) -> Box<dyn Any + Send + Sync>;
+
+impl Glue {
+    /// Create the glue for a box that leaks its value `T` when it is dropped
+    pub(crate) const fn leaked<T>() -> Glue {
+        Self {
+            needs_drop: mem::needs_drop::<T>(),
+            drop: None,
+            finalize: None,
+            vtable: None,
+            any: None,
+        }
+    }
+
+    /// Create the glue for a box that drops its value `T`, which was allocated by a `Box<T>`, when it is dropped
+    pub(crate) const fn owned<T>() -> Glue {
+        /// Safety: `ptr` must point to a valid `T` that was allocated by a `Box<T>`.
+        unsafe fn drop<T>(ptr: NonNull<()>) {
+            mem::drop(Box::from_raw(ptr.cast::<T>().as_ptr()))
+        }
+
+        Self {
+            drop: Some(drop::<T>),
+            ..Self::leaked::<T>()
+        }
+    }
+
+    /// Create the glue for a box that leaks its value `T` when it is dropped, and is allocated as a `Finalized<T>`
+    pub(crate) const fn finalized<T>() -> Glue {
+        /// Safety: `ptr` must point to a valid `Finalized<T>`.
+        unsafe fn finalize<T>(ptr: NonNull<()>) {
+            let finalized = ptr.cast::<Finalized<T>>().as_ref();
+            (finalized.finalizer)(&finalized.value)
+        }
+
+        Self {
+            finalize: Some(finalize::<T>),
+            ..Self::leaked::<T>()
+        }
+    }
+
+    /// Create the glue for a box that drops its value `T`, which was allocated by a `Box<T>`, and can be turned into a `Box<dyn Any + Send + Sync>`
+    pub(crate) const fn any_sync<T: Any + Send + Sync>() -> Glue {
+        /// Safety: `ptr` must point to a valid `T` that was allocated by a `Box<T>`.
+        unsafe fn any<T: Any + Send + Sync>(ptr: NonNull<()>) -> Box<dyn Any + Send + Sync> {
+            Box::from_raw(ptr.cast::<T>().as_ptr())
+        }
+
+        Self {
+            any: Some(any::<T>),
+            ..Self::owned::<T>()
+        }
+    }
+}
+
+/// The allocation of an erased box created using `ErasedBox::with_finalizer`, which stores the finalizer after the value.
+/// The value is the first field, so a pointer to the allocation is a pointer to the value.
+#[repr(C)]
+pub(crate) struct Finalized<T> {
+    pub(crate) value: T,
+    pub(crate) finalizer: fn(&T),
+}
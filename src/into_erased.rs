@@ -0,0 +1,102 @@
+use crate::{Erased, ErasedBox, ErasedMut};
+
+/// A value that can be erased, such as `&'a T`, `&'a mut T` or `Box<T>`.
+///
+/// This allows writing code that is generic over how a value is supplied, and erasing it uniformly.
+/// The erased type `Out<'a>` may borrow from `Self` for any lifetime `'a` that `Self` outlives.
+///
+/// Example:
+/// ```rust
+/// use erased::{Erased, IntoErased};
+///
+/// fn erase_all<'a, V: IntoErased + 'a>(values: Vec<V>) -> Vec<V::Out<'a>> {
+///     values.into_iter().map(|v| v.into_erased()).collect()
+/// }
+///
+/// let erased: Vec<Erased> = erase_all(vec![&1u32, &2u32]);
+/// // SAFETY: Above we only erased `u32`s.
+/// assert_eq!(unsafe { *erased[1].get::<u32>() }, 2);
+/// ```
+pub trait IntoErased {
+    /// The erased type, borrowing from `Self` for `'a`
+    type Out<'a>
+    where
+        Self: 'a;
+
+    /// Erase the type of this value
+    fn into_erased<'a>(self) -> Self::Out<'a>
+    where
+        Self: 'a;
+}
+
+impl<T> IntoErased for &T {
+    type Out<'a>
+        = Erased<'a>
+    where
+        Self: 'a;
+
+    fn into_erased<'a>(self) -> Erased<'a>
+    where
+        Self: 'a,
+    {
+        Erased::new(self)
+    }
+}
+
+impl<T> IntoErased for &mut T {
+    type Out<'a>
+        = ErasedMut<'a>
+    where
+        Self: 'a;
+
+    fn into_erased<'a>(self) -> ErasedMut<'a>
+    where
+        Self: 'a,
+    {
+        ErasedMut::new(self)
+    }
+}
+
+impl<T> IntoErased for Box<T> {
+    type Out<'a>
+        = ErasedBox
+    where
+        Self: 'a;
+
+    fn into_erased<'a>(self) -> ErasedBox
+    where
+        Self: 'a,
+    {
+        ErasedBox::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Erased, ErasedBox, ErasedMut, IntoErased};
+
+    fn erase<'a, V: IntoErased + 'a>(value: V) -> V::Out<'a> {
+        value.into_erased()
+    }
+
+    #[test]
+    fn ref_test() {
+        let r1 = &5usize;
+        let erased: Erased = erase(r1);
+        assert_eq!(unsafe { erased.get::<usize>() }, r1);
+    }
+
+    #[test]
+    fn mut_ref_test() {
+        let r1 = &mut 5usize;
+        let mut erased: ErasedMut = erase(&mut *r1);
+        *unsafe { erased.get::<usize>() } = 42;
+        assert_eq!(*r1, 42);
+    }
+
+    #[test]
+    fn box_test() {
+        let erased: ErasedBox = erase(Box::new(5usize));
+        assert_eq!(*unsafe { erased.into_inner::<usize>() }, 5);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::Erased;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// A handle to the `T` behind an erased reference, created using `Erased::lazy_get`.
+///
+/// The cast to `T` is deferred until the handle is dereferenced, and is performed again on every access, since it is only a pointer cast.
+/// The type `T` is fixed once, when the handle is created, so it does not need to be repeated at every access.
+///
+/// Example:
+/// ```rust
+/// use erased::Erased;
+///
+/// let value = String::from("Hello World");
+/// let erased = Erased::new(&value);
+///
+/// // Safety: Matches the type of `value` exactly, which was used to create the `erased` value
+/// let lazy = unsafe { erased.lazy_get::<String>() };
+/// assert_eq!(lazy.len(), 11);
+/// assert!(lazy.starts_with("Hello"));
+/// ```
+pub struct LazyErased<'a, T> {
+    erased: Erased<'a>,
+    phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T> LazyErased<'a, T> {
+    /// Safety: `erased` must have been created from a `T`.
+    pub(crate) unsafe fn new(erased: Erased<'a>) -> Self {
+        Self {
+            erased,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the erased reference this handle was created from
+    pub fn erased(&self) -> Erased<'a> {
+        self.erased
+    }
+}
+
+impl<T> Deref for LazyErased<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: This handle was created from an erased reference to a `T` in `Erased::lazy_get`.
+        unsafe { self.erased.get::<T>() }
+    }
+}
+
+impl<T> Clone for LazyErased<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LazyErased<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for LazyErased<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Erased;
+
+    #[test]
+    fn repeated_access_test() {
+        let values = vec![1u32, 2, 3];
+        let erased = Erased::new(&values);
+        let lazy = unsafe { erased.lazy_get::<Vec<u32>>() };
+
+        assert_eq!(lazy.len(), 3);
+        assert_eq!(lazy.iter().sum::<u32>(), 6);
+        assert_eq!(lazy[1], 2);
+        let copy = lazy;
+        assert_eq!(*copy, *lazy);
+        assert_eq!(format!("{lazy:?}"), "[1, 2, 3]");
+        assert_eq!(lazy.erased().as_c_void(), erased.as_c_void());
+    }
+}
@@ -1,9 +1,87 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
+// Allows the derive macros to refer to this crate as `::erased` in its own tests.
+extern crate self as erased;
+
+#[cfg(feature = "access_log")]
+mod access_log;
+#[cfg(feature = "checked")]
+mod checked;
+mod deferred_call;
+mod detached_erased_mut;
+mod drop_queue;
+mod epoch_erased;
 mod erased_box;
+mod erased_bytes;
+mod erased_cursor;
+mod erased_ffi;
+mod erased_inline;
+mod erased_map;
 mod erased_mut_ref;
+mod erased_mutex;
+mod erased_pin_mut;
+mod erased_pool;
 mod erased_ref;
+mod erased_ref_cell;
+mod erased_slice;
+mod erased_tree_walker;
+mod erased_unsized_box;
+mod erased_unsized_ref;
+mod erased_vtable;
+mod erased_with_meta;
+mod fat_ptr;
+mod gen_erased;
+mod glue;
+mod into_erased;
+mod lazy_erased;
+#[cfg(feature = "prim_int")]
+mod prim_int;
+#[cfg(feature = "checked")]
+mod reflect;
+mod restricted_erased;
+mod safe_to_erase;
+mod tagged_erased;
+mod typed_slot;
+mod versioned_erased_box;
 
-pub use erased_box::ErasedBox;
+#[cfg(feature = "access_log")]
+pub use access_log::{dump_access_log, AccessLogEntry, ACCESS_LOG_CAPACITY};
+pub use deferred_call::DeferredCall;
+pub use detached_erased_mut::DetachedErasedMut;
+pub use drop_queue::DropQueue;
+pub use epoch_erased::EpochErased;
+#[cfg(feature = "checked")]
+pub use erased_box::type_tags;
+pub use erased_box::{drop_all, into_any_vec, ErasedBox};
+pub use erased_bytes::ErasedBytes;
+pub use erased_cursor::ErasedCursor;
+#[cfg(feature = "derive")]
+pub use erased_derive::ErasedFields;
+pub use erased_ffi::ErasedFfi;
+pub use erased_inline::ErasedInline;
+pub use erased_map::{ErasedEntry, ErasedMap};
 pub use erased_mut_ref::ErasedMut;
+pub use erased_mutex::{ErasedMutex, ErasedMutexGuard};
+pub use erased_pin_mut::ErasedPinMut;
+pub use erased_pool::ErasedPool;
 pub use erased_ref::Erased;
+pub use erased_ref_cell::{ErasedRefCell, ErasedRefMut};
+pub use erased_slice::ErasedSlice;
+pub use erased_tree_walker::{ErasedTreeWalker, TraversalOrder};
+pub use erased_unsized_box::ErasedUnsizedBox;
+pub use erased_unsized_ref::ErasedUnsized;
+pub use erased_vtable::ErasedVtable;
+pub use erased_with_meta::ErasedWithMeta;
+pub use gen_erased::GenErased;
+pub use into_erased::IntoErased;
+pub use lazy_erased::LazyErased;
+#[cfg(feature = "prim_int")]
+pub use prim_int::PrimInt;
+#[cfg(feature = "checked")]
+pub use reflect::{DebugNode, Reflect, Reflection, ReflectorRegistry};
+pub use restricted_erased::{AllowedType, AllowedTypes, RestrictedErased};
+pub use safe_to_erase::SafeToErase;
+pub use tagged_erased::{ErasedTag, TaggedErased};
+pub use typed_slot::{TypedSlot, TypedSlots};
+pub use versioned_erased_box::VersionedErasedBox;
@@ -0,0 +1,34 @@
+/// A primitive integer type, which can be read from erased bytes with an explicit endianness using `Erased::read_int_be` and `Erased::read_int_le`.
+///
+/// This trait is sealed, it is implemented for all primitive integer types.
+pub trait PrimInt: Copy + sealed::Sealed {
+    /// Convert an integer from big endian to the native endianness
+    fn from_be(x: Self) -> Self;
+
+    /// Convert an integer from little endian to the native endianness
+    fn from_le(x: Self) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_prim_int {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl PrimInt for $t {
+                fn from_be(x: Self) -> Self {
+                    <$t>::from_be(x)
+                }
+
+                fn from_le(x: Self) -> Self {
+                    <$t>::from_le(x)
+                }
+            }
+        )*
+    };
+}
+
+impl_prim_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
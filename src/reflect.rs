@@ -0,0 +1,214 @@
+use crate::Erased;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A type of which the fields can be listed, so an erased reference to it can be turned into a `DebugNode` tree using `Erased::debug_tree`.
+///
+/// Each field should be erased using `Erased::new_checked`, so its type can be looked up in the `ReflectorRegistry`.
+pub trait Reflect: 'static {
+    /// List the fields of this value
+    fn reflect(&self) -> Reflection<'_>;
+}
+
+/// The result of reflecting on a value, either a formatted leaf value, or a list of named fields.
+#[derive(Debug)]
+pub enum Reflection<'a> {
+    /// A leaf value, formatted as a string
+    Value(String),
+    /// The names of the fields of a struct, together with erased references to them
+    Fields(Vec<(&'static str, Erased<'a>)>),
+}
+
+/// A function that reflects on an erased value of a specific type.
+type Reflector = for<'a> fn(Erased<'a>) -> Reflection<'a>;
+
+/// A registry of reflectors for types, used by `Erased::debug_tree` to build a tree of the fields of an erased value.
+///
+/// Example:
+/// ```rust
+/// use erased::{Erased, Reflect, Reflection, ReflectorRegistry};
+///
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl Reflect for Point {
+///     fn reflect(&self) -> Reflection<'_> {
+///         Reflection::Fields(vec![("x", Erased::new_checked(&self.x)), ("y", Erased::new_checked(&self.y))])
+///     }
+/// }
+///
+/// let mut reflectors = ReflectorRegistry::new();
+/// reflectors.register::<Point>();
+/// reflectors.register_debug::<i32>();
+///
+/// let point = Point { x: 1, y: -2 };
+/// let tree = Erased::new_checked(&point).debug_tree(&reflectors).unwrap();
+/// assert_eq!(tree.to_string(), r#"{"x": 1, "y": -2}"#);
+/// ```
+#[derive(Debug, Default)]
+pub struct ReflectorRegistry {
+    reflectors: HashMap<TypeId, Reflector>,
+}
+
+impl ReflectorRegistry {
+    /// Create an empty registry
+    pub fn new() -> ReflectorRegistry {
+        Self::default()
+    }
+
+    /// Register the reflector of `T`, which lists its fields using `Reflect`
+    pub fn register<T: Reflect>(&mut self) {
+        fn reflect<T: Reflect>(erased: Erased<'_>) -> Reflection<'_> {
+            erased.get_checked::<T>().reflect()
+        }
+
+        self.reflectors.insert(TypeId::of::<T>(), reflect::<T>);
+    }
+
+    /// Register a reflector for `T`, which formats it as a leaf value using `Debug`
+    pub fn register_debug<T: fmt::Debug + 'static>(&mut self) {
+        fn reflect<T: fmt::Debug + 'static>(erased: Erased<'_>) -> Reflection<'_> {
+            Reflection::Value(format!("{:?}", erased.get_checked::<T>()))
+        }
+
+        self.reflectors.insert(TypeId::of::<T>(), reflect::<T>);
+    }
+
+    /// Build the debug tree of `erased`, recursing into its fields.
+    /// Returns `None` if `erased` was not created using `Erased::new_checked`, or if no reflector is registered for its type.
+    pub(crate) fn debug_tree(&self, erased: Erased<'_>) -> Option<DebugNode> {
+        let reflector = self.reflectors.get(&erased.type_info?.id)?;
+        Some(match reflector(erased) {
+            Reflection::Value(value) => DebugNode::Value(value),
+            Reflection::Fields(fields) => DebugNode::Struct(
+                fields
+                    .into_iter()
+                    .map(|(name, field)| {
+                        (name, self.debug_tree(field).unwrap_or(DebugNode::Opaque))
+                    })
+                    .collect(),
+            ),
+        })
+    }
+}
+
+/// A tree of the fields of an erased value, created using `Erased::debug_tree`.
+///
+/// The `Display` implementation formats the tree similar to JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DebugNode {
+    /// A leaf value, formatted as a string
+    Value(String),
+    /// A struct, with the names and debug trees of its fields
+    Struct(Vec<(&'static str, DebugNode)>),
+    /// A field for which no reflector is registered, or which was not created using `Erased::new_checked`
+    Opaque,
+}
+
+impl fmt::Display for DebugNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DebugNode::Value(value) => f.write_str(value),
+            DebugNode::Struct(fields) => {
+                f.write_str("{")?;
+                for (i, (name, node)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{name:?}: {node}")?;
+                }
+                f.write_str("}")
+            }
+            DebugNode::Opaque => f.write_str("null"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DebugNode, Erased, Reflect, Reflection, ReflectorRegistry};
+
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    impl Reflect for Person {
+        fn reflect(&self) -> Reflection<'_> {
+            Reflection::Fields(vec![
+                ("name", Erased::new_checked(&self.name)),
+                ("age", Erased::new_checked(&self.age)),
+            ])
+        }
+    }
+
+    struct Team {
+        lead: Person,
+        size: usize,
+    }
+
+    impl Reflect for Team {
+        fn reflect(&self) -> Reflection<'_> {
+            Reflection::Fields(vec![
+                ("lead", Erased::new_checked(&self.lead)),
+                ("size", Erased::new_checked(&self.size)),
+            ])
+        }
+    }
+
+    fn registry() -> ReflectorRegistry {
+        let mut reflectors = ReflectorRegistry::new();
+        reflectors.register::<Person>();
+        reflectors.register::<Team>();
+        reflectors.register_debug::<String>();
+        reflectors.register_debug::<u32>();
+        reflectors
+    }
+
+    #[test]
+    fn two_fields_test() {
+        let person = Person {
+            name: String::from("Alice"),
+            age: 42,
+        };
+        let tree = Erased::new_checked(&person).debug_tree(&registry());
+        assert_eq!(
+            tree,
+            Some(DebugNode::Struct(vec![
+                ("name", DebugNode::Value(String::from("\"Alice\""))),
+                ("age", DebugNode::Value(String::from("42"))),
+            ]))
+        );
+        assert_eq!(tree.unwrap().to_string(), r#"{"name": "Alice", "age": 42}"#);
+    }
+
+    #[test]
+    fn nested_test() {
+        let team = Team {
+            lead: Person {
+                name: String::from("Bob"),
+                age: 7,
+            },
+            size: 3,
+        };
+        let tree = Erased::new_checked(&team).debug_tree(&registry()).unwrap();
+        assert_eq!(
+            tree.to_string(),
+            r#"{"lead": {"name": "Bob", "age": 7}, "size": null}"#
+        );
+    }
+
+    #[test]
+    fn unregistered_test() {
+        let reflectors = registry();
+        assert_eq!(Erased::new_checked(&5usize).debug_tree(&reflectors), None);
+        assert_eq!(Erased::new(&5u32).debug_tree(&reflectors), None);
+        assert_eq!(
+            Erased::new_checked(&5u32).debug_tree(&reflectors),
+            Some(DebugNode::Value(String::from("5")))
+        );
+    }
+}
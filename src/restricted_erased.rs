@@ -0,0 +1,182 @@
+use crate::Erased;
+use std::any::type_name;
+use std::marker::PhantomData;
+
+/// A closed set of types, which can be used to restrict the types that can be stored in a `RestrictedErased`.
+///
+/// Sets of types are defined using the `allowed_types!` macro.
+pub trait AllowedTypes: 'static {}
+
+/// A type that is a member of the set of types `S`, with a tag that identifies it within that set.
+///
+/// This trait is implemented by the `allowed_types!` macro.
+///
+/// # Safety
+/// No two types may implement this trait for the same `S` with the same `TAG`.
+pub unsafe trait AllowedType<S: AllowedTypes>: 'static {
+    /// The tag identifying this type within `S`
+    const TAG: u32;
+}
+
+/// Define a closed set of types, for use with `RestrictedErased`.
+///
+/// This defines a type with the given name, which implements `AllowedTypes`, and implements `AllowedType` for each of the given types with a unique tag.
+/// A type can not be listed twice.
+///
+/// Example:
+/// ```rust
+/// use erased::{allowed_types, RestrictedErased};
+///
+/// allowed_types! {
+///     pub struct Config { u32, String }
+/// }
+///
+/// let port = 8080u32;
+/// let erased: RestrictedErased<Config> = RestrictedErased::new(&port);
+/// assert!(erased.is::<u32>());
+/// assert_eq!(*erased.get_checked::<u32>(), 8080);
+/// ```
+#[macro_export]
+macro_rules! allowed_types {
+    ($vis:vis struct $name:ident { $($ty:ty),* $(,)? }) => {
+        $vis struct $name;
+
+        impl $crate::AllowedTypes for $name {}
+
+        $crate::allowed_types!(@impl $name, 0; $($ty,)*);
+    };
+    (@impl $name:ident, $tag:expr; $ty:ty, $($rest:ty,)*) => {
+        // Safety: Each type gets a unique tag, since the tag is incremented for every type in the set.
+        unsafe impl $crate::AllowedType<$name> for $ty {
+            const TAG: u32 = $tag;
+        }
+
+        $crate::allowed_types!(@impl $name, $tag + 1; $($rest,)*);
+    };
+    (@impl $name:ident, $tag:expr;) => {};
+}
+
+/// An erased reference to a value `&'a T`, where `T` must be a member of the set of types `S`.
+///
+/// The tag of `T` is stored alongside the reference, so recovering the reference as a different type of the set panics,
+/// and recovering the reference as a type outside of the set fails to compile.
+/// This makes recovery safe, and restricts erasure and recovery to a vetted list of types.
+///
+/// Using a type outside of the set fails to compile:
+/// ```rust,compile_fail
+/// use erased::{allowed_types, RestrictedErased};
+///
+/// allowed_types! {
+///     struct Config { u32, String }
+/// }
+///
+/// let port = 8080u32;
+/// let erased: RestrictedErased<Config> = RestrictedErased::new(&port);
+/// erased.get_checked::<u64>();
+/// ```
+pub struct RestrictedErased<'a, S: AllowedTypes> {
+    erased: Erased<'a>,
+    tag: u32,
+    phantom: PhantomData<S>,
+}
+
+impl<'a, S: AllowedTypes> RestrictedErased<'a, S> {
+    /// Create a new erased reference from a reference to `T`, which must be a member of `S`
+    pub fn new<T: AllowedType<S>>(t: &'a T) -> RestrictedErased<'a, S> {
+        Self {
+            erased: Erased::new(t),
+            tag: T::TAG,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Check whether the erased reference was created from a `T`
+    pub fn is<T: AllowedType<S>>(&self) -> bool {
+        self.tag == T::TAG
+    }
+
+    /// Get a reference to `T` back from the erased reference, which must be a member of `S`.
+    ///
+    /// # Panics
+    /// Panics if the erased reference was created from a different type than `T`.
+    pub fn get_checked<T: AllowedType<S>>(&self) -> &'a T {
+        assert!(
+            self.is::<T>(),
+            "Restricted erased value with tag `{}` was recovered as type `{}`",
+            self.tag,
+            type_name::<T>()
+        );
+        // Safety: This reference was created from a type with the tag of `T` in `S`, and from the safety comment of `AllowedType` that type is `T`.
+        unsafe { self.erased.get::<T>() }
+    }
+
+    /// Get the unrestricted erased reference
+    pub fn erased(&self) -> Erased<'a> {
+        self.erased
+    }
+}
+
+impl<S: AllowedTypes> Clone for RestrictedErased<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: AllowedTypes> Copy for RestrictedErased<'_, S> {}
+
+impl<S: AllowedTypes> std::fmt::Debug for RestrictedErased<'_, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestrictedErased")
+            .field("erased", &self.erased)
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AllowedType, RestrictedErased};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    allowed_types! {
+        struct Config { u32, String, &'static str }
+    }
+
+    #[test]
+    fn tags_test() {
+        assert_eq!(<u32 as AllowedType<Config>>::TAG, 0);
+        assert_eq!(<String as AllowedType<Config>>::TAG, 1);
+        assert_eq!(<&str as AllowedType<Config>>::TAG, 2);
+    }
+
+    #[test]
+    fn recover_test() {
+        let port = 8080u32;
+        let name = String::from("server");
+        let vec: Vec<RestrictedErased<Config>> = vec![
+            RestrictedErased::new(&port),
+            RestrictedErased::new(&name),
+            RestrictedErased::new(&"Hello World"),
+        ];
+
+        assert!(vec[0].is::<u32>());
+        assert!(!vec[0].is::<String>());
+        assert_eq!(*vec[0].get_checked::<u32>(), 8080);
+        assert_eq!(vec[1].get_checked::<String>(), "server");
+        assert_eq!(*vec[2].get_checked::<&str>(), "Hello World");
+    }
+
+    #[test]
+    fn mismatch_test() {
+        let port = 8080u32;
+        let erased: RestrictedErased<Config> = RestrictedErased::new(&port);
+        let err = catch_unwind(AssertUnwindSafe(|| {
+            erased.get_checked::<String>();
+        }))
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            "Restricted erased value with tag `0` was recovered as type `alloc::string::String`"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+/// A marker trait for types that have been vetted to be erased, using `Erased::new_safe` or `ErasedBox::new_safe`.
+///
+/// This trait is opt-in, it is not implemented for any types by this crate.
+/// A codebase can use the `new_safe` constructors instead of the `new` constructors to restrict which types may be erased,
+/// for example to forbid erasing types with a non-obvious `Drop` implementation, which would be leaked by an `ErasedBox`.
+///
+/// Example:
+/// ```rust
+/// use erased::{Erased, SafeToErase};
+///
+/// struct Position(f32, f32);
+/// impl SafeToErase for Position {}
+///
+/// let position = Position(1.0, 2.0);
+/// let erased = Erased::new_safe(&position);
+/// ```
+///
+/// Erasing a type that does not implement `SafeToErase` fails to compile:
+/// ```rust,compile_fail
+/// use erased::Erased;
+///
+/// struct Connection;
+///
+/// let connection = Connection;
+/// let erased = Erased::new_safe(&connection);
+/// ```
+pub trait SafeToErase {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Erased, ErasedBox, SafeToErase};
+
+    #[derive(Debug, PartialEq)]
+    struct Position(u32, u32);
+    impl SafeToErase for Position {}
+
+    #[test]
+    fn new_safe_test() {
+        let position = Position(1, 2);
+        let erased = Erased::new_safe(&position);
+        assert_eq!(unsafe { erased.get::<Position>() }, &Position(1, 2));
+
+        let erased = ErasedBox::new_safe(Box::new(Position(3, 4)));
+        assert_eq!(*unsafe { erased.into_inner::<Position>() }, Position(3, 4));
+    }
+}
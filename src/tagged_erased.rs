@@ -0,0 +1,106 @@
+use crate::Erased;
+
+/// A type with a unique, compile-time integer tag, which can be stored in a `TaggedErased`.
+///
+/// # Safety
+/// No two types may implement this trait with the same `TAG`.
+pub unsafe trait ErasedTag: 'static {
+    /// The tag identifying this type
+    const TAG: u32;
+}
+
+/// An erased reference to a value `&'a T`, where the type `T` is identified by the compile-time tag `TAG`.
+///
+/// Since the tag is part of the type, both creating and recovering the reference check at compile time that the tag of `T` is `TAG`.
+/// This makes recovery safe, and allows collections to be statically partitioned by tag.
+///
+/// Example:
+/// ```rust
+/// use erased::{ErasedTag, TaggedErased};
+///
+/// struct Position(f32, f32);
+/// // Safety: No other type uses the tag `0`.
+/// unsafe impl ErasedTag for Position {
+///     const TAG: u32 = 0;
+/// }
+///
+/// let positions: Vec<TaggedErased<0>> = vec![TaggedErased::new(&Position(1.0, 2.0))];
+/// assert_eq!(positions[0].get::<Position>().1, 2.0);
+/// ```
+///
+/// Using a type with a different tag fails to compile:
+/// ```rust,compile_fail
+/// use erased::{ErasedTag, TaggedErased};
+///
+/// struct Position(f32, f32);
+/// // Safety: No other type uses the tag `0`.
+/// unsafe impl ErasedTag for Position {
+///     const TAG: u32 = 0;
+/// }
+///
+/// let position: TaggedErased<1> = TaggedErased::new(&Position(1.0, 2.0));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct TaggedErased<'a, const TAG: u32> {
+    erased: Erased<'a>,
+}
+
+impl<'a, const TAG: u32> TaggedErased<'a, TAG> {
+    /// Create a new erased reference from a reference to `T`, which must have the tag `TAG`
+    pub fn new<T: ErasedTag>(t: &'a T) -> TaggedErased<'a, TAG> {
+        const {
+            assert!(
+                T::TAG == TAG,
+                "Type does not have the tag of this `TaggedErased`"
+            );
+        }
+        Self {
+            erased: Erased::new(t),
+        }
+    }
+
+    /// Get a reference to `T` back from the erased reference, which must have the tag `TAG`
+    pub fn get<T: ErasedTag>(&self) -> &'a T {
+        const {
+            assert!(
+                T::TAG == TAG,
+                "Type does not have the tag of this `TaggedErased`"
+            );
+        }
+        // Safety: This reference was created from a type with tag `TAG`, and from the safety comment of `ErasedTag` that type is `T`.
+        unsafe { self.erased.get::<T>() }
+    }
+
+    /// Get the untagged erased reference
+    pub fn erased(&self) -> Erased<'a> {
+        self.erased
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErasedTag, TaggedErased};
+
+    struct Position(u32, u32);
+    unsafe impl ErasedTag for Position {
+        const TAG: u32 = 0;
+    }
+
+    struct Name(String);
+    unsafe impl ErasedTag for Name {
+        const TAG: u32 = 1;
+    }
+
+    #[test]
+    fn basic_test() {
+        let position = Position(1, 2);
+        let name = Name(String::from("Hello World"));
+
+        let positions: Vec<TaggedErased<0>> = vec![TaggedErased::new(&position)];
+        let names: Vec<TaggedErased<1>> = vec![TaggedErased::new(&name)];
+
+        let Position(x, y) = positions[0].get::<Position>();
+        assert_eq!((*x, *y), (1, 2));
+        assert_eq!(names[0].get::<Name>().0, "Hello World");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::ErasedBox;
+use std::any::type_name;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A key for the slot `ID` of a `TypedSlots`, which holds values of type `T`.
+///
+/// The key is zero-sized and ties the slot index `ID` to the type `T` at compile time,
+/// so values can be stored in and recovered from a `TypedSlots` without any runtime type checks.
+pub struct TypedSlot<const ID: usize, T> {
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<const ID: usize, T> TypedSlot<ID, T> {
+    /// Create the key for slot `ID`, holding values of type `T`.
+    ///
+    /// # Safety
+    /// Every key for slot `ID` that is used with the same `TypedSlots` must be created with exactly the same `T`.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    pub const unsafe fn new() -> TypedSlot<ID, T> {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<const ID: usize, T> Clone for TypedSlot<ID, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const ID: usize, T> Copy for TypedSlot<ID, T> {}
+
+impl<const ID: usize, T> fmt::Debug for TypedSlot<ID, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypedSlot<{ID}, {}>", type_name::<T>())
+    }
+}
+
+/// A fixed-size registry of `N` slots, which each hold a value of a type that is known at compile time.
+///
+/// Slots are identified by a `TypedSlot` key, which fixes both the index and the type of the slot.
+/// Using a slot index that is out of bounds fails to compile.
+/// The values in the slots are dropped when they are replaced or when the registry is dropped.
+///
+/// Example:
+/// ```rust
+/// use erased::{TypedSlot, TypedSlots};
+///
+/// // Safety: No other keys for slot `0` and `1` exist.
+/// const NAME: TypedSlot<0, String> = unsafe { TypedSlot::new() };
+/// const PORT: TypedSlot<1, u16> = unsafe { TypedSlot::new() };
+///
+/// let mut slots = TypedSlots::<2>::new();
+/// slots.set(NAME, String::from("server"));
+/// slots.set(PORT, 8080);
+///
+/// assert_eq!(slots.get(NAME).map(String::as_str), Some("server"));
+/// assert_eq!(slots.get(PORT), Some(&8080));
+/// ```
+///
+/// Using a slot index that is out of bounds fails to compile:
+/// ```rust,compile_fail
+/// use erased::{TypedSlot, TypedSlots};
+///
+/// // Safety: No other keys for slot `2` exist.
+/// const PORT: TypedSlot<2, u16> = unsafe { TypedSlot::new() };
+///
+/// let mut slots = TypedSlots::<2>::new();
+/// slots.set(PORT, 8080);
+/// ```
+#[derive(Debug)]
+pub struct TypedSlots<const N: usize> {
+    slots: [Option<ErasedBox>; N],
+}
+
+impl<const N: usize> TypedSlots<N> {
+    /// Create a registry with all slots empty
+    pub fn new() -> TypedSlots<N> {
+        Self {
+            slots: [const { None }; N],
+        }
+    }
+
+    /// Check at compile time that slot `ID` is in bounds
+    const fn assert_in_bounds<const ID: usize>() {
+        const {
+            assert!(ID < N, "Slot is out of bounds of this `TypedSlots`");
+        }
+    }
+
+    /// Store a value in slot `ID`, returning the previous value if the slot was not empty
+    pub fn set<const ID: usize, T: 'static>(
+        &mut self,
+        slot: TypedSlot<ID, T>,
+        value: T,
+    ) -> Option<T> {
+        Self::assert_in_bounds::<ID>();
+        let previous = self.take(slot);
+        self.slots[ID] = Some(ErasedBox::new_owned(Box::new(value)));
+        previous
+    }
+
+    /// Get a reference to the value in slot `ID`, if the slot is not empty
+    pub fn get<const ID: usize, T: 'static>(&self, _slot: TypedSlot<ID, T>) -> Option<&T> {
+        Self::assert_in_bounds::<ID>();
+        // Safety: From the safety comment of `TypedSlot::new`, values in slot `ID` are always of type `T`.
+        self.slots[ID]
+            .as_ref()
+            .map(|erased| unsafe { erased.get_ref::<T>() })
+    }
+
+    /// Get a mutable reference to the value in slot `ID`, if the slot is not empty
+    pub fn get_mut<const ID: usize, T: 'static>(
+        &mut self,
+        _slot: TypedSlot<ID, T>,
+    ) -> Option<&mut T> {
+        Self::assert_in_bounds::<ID>();
+        // Safety: From the safety comment of `TypedSlot::new`, values in slot `ID` are always of type `T`.
+        self.slots[ID]
+            .as_mut()
+            .map(|erased| unsafe { erased.get_mut::<T>() })
+    }
+
+    /// Take the value out of slot `ID`, leaving the slot empty
+    pub fn take<const ID: usize, T: 'static>(&mut self, _slot: TypedSlot<ID, T>) -> Option<T> {
+        Self::assert_in_bounds::<ID>();
+        // Safety: From the safety comment of `TypedSlot::new`, values in slot `ID` are always of type `T`.
+        self.slots[ID]
+            .take()
+            .map(|erased| *unsafe { erased.into_inner::<T>() })
+    }
+}
+
+impl<const N: usize> Default for TypedSlots<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TypedSlot, TypedSlots};
+    use std::rc::Rc;
+
+    const NAME: TypedSlot<0, String> = unsafe { TypedSlot::new() };
+    const COUNT: TypedSlot<1, Rc<u32>> = unsafe { TypedSlot::new() };
+
+    #[test]
+    fn two_slots_test() {
+        let mut slots = TypedSlots::<2>::new();
+        assert_eq!(slots.get(NAME), None);
+
+        assert_eq!(slots.set(NAME, String::from("Hello")), None);
+        assert_eq!(slots.set(COUNT, Rc::new(5u32)), None);
+        slots.get_mut(NAME).unwrap().push_str(" World");
+
+        assert_eq!(slots.get(NAME).unwrap(), "Hello World");
+        assert_eq!(**slots.get(COUNT).unwrap(), 5);
+        assert_eq!(slots.take(NAME).unwrap(), "Hello World");
+        assert_eq!(slots.get(NAME), None);
+        assert_eq!(**slots.get(COUNT).unwrap(), 5);
+    }
+
+    #[test]
+    fn drop_test() {
+        let count = Rc::new(5u32);
+        let mut slots = TypedSlots::<2>::default();
+        assert_eq!(slots.set(COUNT, count.clone()), None);
+        assert_eq!(Rc::strong_count(&count), 2);
+
+        let previous = slots.set(COUNT, count.clone()).unwrap();
+        assert!(Rc::ptr_eq(&previous, &count));
+        drop(previous);
+        assert_eq!(Rc::strong_count(&count), 2);
+
+        drop(slots);
+        assert_eq!(Rc::strong_count(&count), 1);
+    }
+}
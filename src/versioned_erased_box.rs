@@ -0,0 +1,108 @@
+use crate::ErasedBox;
+
+/// An erased box `Box<T>`, stored together with a schema hash that is checked when the value is recovered.
+///
+/// The schema hash is computed by the caller, for example from the layout and version of `T`.
+/// This helps to catch recovering values that were written with an incompatible version of `T`, for example after they crossed a process or file boundary.
+///
+/// # Warning
+/// Like `ErasedBox::new`, a versioned box created using `VersionedErasedBox::new` **leaks** the value when it is dropped.
+/// Use `VersionedErasedBox::from_erased` with a box created using `ErasedBox::new_owned` to drop the value instead.
+///
+/// Example:
+/// ```rust
+/// use erased::VersionedErasedBox;
+///
+/// const SCHEMA_V1: u64 = 1;
+/// const SCHEMA_V2: u64 = 2;
+///
+/// let versioned = VersionedErasedBox::new(Box::new(5u32), SCHEMA_V1);
+///
+/// // Safety: The type matches the type the box was created with.
+/// let versioned = unsafe { versioned.into_inner_versioned::<u32>(SCHEMA_V2) }.unwrap_err();
+/// let value = unsafe { versioned.into_inner_versioned::<u32>(SCHEMA_V1) }.unwrap();
+/// assert_eq!(*value, 5);
+/// ```
+#[derive(Debug)]
+pub struct VersionedErasedBox {
+    erased: ErasedBox,
+    schema_hash: u64,
+}
+
+impl VersionedErasedBox {
+    /// Create a new versioned box from a `Box<T>` and the schema hash of `T`
+    pub fn new<T>(t: Box<T>, schema_hash: u64) -> VersionedErasedBox {
+        Self::from_erased(ErasedBox::new(t), schema_hash)
+    }
+
+    /// Create a new versioned box from an erased box and the schema hash of its value
+    pub fn from_erased(erased: ErasedBox, schema_hash: u64) -> VersionedErasedBox {
+        Self {
+            erased,
+            schema_hash,
+        }
+    }
+
+    /// Get the schema hash this box was created with
+    pub fn schema_hash(&self) -> u64 {
+        self.schema_hash
+    }
+
+    /// Get the erased box without the schema hash
+    pub fn into_erased(self) -> ErasedBox {
+        self.erased
+    }
+
+    /// Get a normal box `Box<T>` back from this box, if its schema hash matches `expected_hash`.
+    /// Returns this box back if the schema hash does not match.
+    ///
+    /// Note that a matching schema hash does not guarantee that the type matches, so this function is still unsafe.
+    ///
+    /// # Safety
+    /// The generic argument `T` of this function must match the `T` that was used to create this box exactly.
+    /// Pay specific attention that any lifetime parameters of `T` match.
+    ///
+    /// It is **strongly recommended** to provide `T` explicitly, even if it can be inferred. This is to make sure that the value of `T` is not accidentally changed.
+    pub unsafe fn into_inner_versioned<T>(
+        self,
+        expected_hash: u64,
+    ) -> Result<Box<T>, VersionedErasedBox> {
+        if self.schema_hash != expected_hash {
+            return Err(self);
+        }
+        // Safety: Guaranteed by the safety comment of this function.
+        Ok(self.erased.into_inner::<T>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErasedBox, VersionedErasedBox};
+    use std::rc::Rc;
+
+    const SCHEMA_V1: u64 = 0x5eed_0001;
+    const SCHEMA_V2: u64 = 0x5eed_0002;
+
+    #[test]
+    fn matching_hash_test() {
+        let versioned = VersionedErasedBox::new(Box::new((5u32, String::from("Hello"))), SCHEMA_V1);
+        assert_eq!(versioned.schema_hash(), SCHEMA_V1);
+        let value = unsafe { versioned.into_inner_versioned::<(u32, String)>(SCHEMA_V1) }.unwrap();
+        assert_eq!(*value, (5, String::from("Hello")));
+    }
+
+    #[test]
+    fn mismatching_hash_test() {
+        let value = Rc::new(5u32);
+        let erased = ErasedBox::new_owned(Box::new(value.clone()));
+        let versioned = VersionedErasedBox::from_erased(erased, SCHEMA_V1);
+
+        let versioned =
+            unsafe { versioned.into_inner_versioned::<Rc<u32>>(SCHEMA_V2) }.unwrap_err();
+        assert_eq!(versioned.schema_hash(), SCHEMA_V1);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(versioned);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
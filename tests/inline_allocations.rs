@@ -0,0 +1,53 @@
+//! Checks that `ErasedInline` only allocates for values that don't fit inline.
+//! This installs a counting global allocator, so it lives in its own test binary.
+
+use erased::ErasedInline;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts the allocations made on the current thread.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn inline_does_not_allocate() {
+    let before = allocations();
+    let mut erased = ErasedInline::new(5u64);
+    *unsafe { erased.get_mut::<u64>() } += 1;
+    assert_eq!(unsafe { erased.into_inner::<u64>() }, 6);
+    assert_eq!(allocations(), before);
+
+    let erased = ErasedInline::new((1u32, 2usize));
+    assert_eq!(unsafe { erased.into_inner::<(u32, usize)>() }, (1, 2));
+    assert_eq!(allocations(), before);
+}
+
+#[test]
+fn boxed_allocates_once() {
+    let before = allocations();
+    let erased = ErasedInline::new([7u64; 8]);
+    assert_eq!(allocations(), before + 1);
+    assert_eq!(unsafe { erased.into_inner::<[u64; 8]>() }, [7; 8]);
+    assert_eq!(allocations(), before + 1);
+}